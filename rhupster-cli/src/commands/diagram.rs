@@ -0,0 +1,49 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use console::style;
+use rhupster_core::diagram::{self, DiagramFormat};
+use rhupster_core::dsl;
+use rhupster_core::model::PrimaryKey;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct DiagramArgs {
+    /// Domain file (`.rhdl`) to draw
+    file: PathBuf,
+
+    /// Diagram format: mermaid, plantuml (default: from the output extension, else mermaid)
+    #[arg(short, long)]
+    format: Option<DiagramFormat>,
+
+    /// File to write the diagram to instead of printing it
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Id type of entities that don't set their own: u64, i64, uuid-v4, uuid-v7, ulid
+    #[arg(long, default_value = "u64")]
+    primary_key: PrimaryKey,
+}
+
+pub fn run(args: DiagramArgs) -> Result<()> {
+    let model = dsl::parse_file(&args.file)?;
+    let format = args
+        .format
+        .or_else(|| {
+            let extension = args.output.as_ref()?.extension()?.to_str()?;
+            extension.parse().ok()
+        })
+        .unwrap_or_default();
+    let rendered = diagram::render(&model, args.primary_key, format);
+
+    match &args.output {
+        Some(output) => {
+            std::fs::write(output, rendered).with_context(|| format!("Failed to write {}", output.display()))?;
+            println!(
+                "{}",
+                style(format!("Wrote the {} diagram of {} entity(ies) to {}.", format, model.entities.len(), output.display())).green()
+            );
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anyhow::{bail, Result};
+use clap::Args;
+use console::style;
+use rhupster_core::manifest::MANIFEST_FILE_NAME;
+use rhupster_core::model::{Entity, Field};
+
+use crate::prompts::PromptService;
+
+#[derive(Args, Debug)]
+pub struct EntityArgs {
+    /// Entity name in PascalCase, e.g. `Truck`
+    name: String,
+
+    /// Field as `name:type`, repeatable; append `!` for required. Asked interactively when omitted.
+    #[arg(short, long = "field")]
+    fields: Vec<Field>,
+
+    /// Track who created and last changed each row, and keep its change history
+    #[arg(long)]
+    audited: bool,
+
+    /// Keep deleted rows with a `deleted_at` timestamp so they can be restored
+    #[arg(long)]
+    soft_delete: bool,
+
+    /// Reject updates made against a stale copy, using a `version` column
+    #[arg(long)]
+    versioned: bool,
+}
+
+pub fn run(args: EntityArgs) -> Result<()> {
+    let (project_dir, mut manifest) = super::current_project()?;
+
+    if manifest.config.entities.iter().any(|e| e.name == args.name) {
+        bail!("Entity '{}' already exists in this project", args.name);
+    }
+
+    let mut entity = Entity::new(args.name);
+    entity.audited = args.audited;
+    entity.soft_delete = args.soft_delete;
+    entity.versioned = args.versioned;
+    entity.fields = if args.fields.is_empty() {
+        PromptService::new().collect_entity_fields(&entity.name)?
+    } else {
+        args.fields
+    };
+
+    println!("Adding entity '{}' with {} field(s).", entity.name, entity.fields.len());
+    manifest.config.entities.push(entity);
+    super::check_config(&manifest.config)?;
+    manifest.save(&project_dir)?;
+
+    println!("{}", style(format!("Entity saved to {}.", MANIFEST_FILE_NAME)).green());
+    println!("Run `rhupster regenerate` to render it.");
+    Ok(())
+}
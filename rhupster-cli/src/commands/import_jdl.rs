@@ -0,0 +1,29 @@
+use anyhow::Result;
+use clap::Args;
+use console::style;
+use rhupster_core::jdl;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct ImportJdlArgs {
+    /// JHipster `.jdl` file to import
+    file: PathBuf,
+
+    /// Config file to write; TOML, YAML or JSON by extension
+    #[arg(short, long, default_value = "rhupster.toml")]
+    output: PathBuf,
+
+    /// Overwrite the output file if it already exists
+    #[arg(long)]
+    force: bool,
+}
+
+pub fn run(args: ImportJdlArgs) -> Result<()> {
+    let import = jdl::import_file(&args.file)?;
+    for warning in &import.warnings {
+        println!("{} {}:{}: {}", style("warning").yellow().bold(), args.file.display(), warning.span, warning.message);
+    }
+
+    let entities = import.config.entities.as_ref().map_or(0, Vec::len);
+    super::write_import(&import.config, &args.output, args.force, &format!("{} entity(ies)", entities))
+}
@@ -0,0 +1,37 @@
+use anyhow::Result;
+use clap::Args;
+use console::style;
+use rhupster_core::config::RouterStrategy;
+use rhupster_core::openapi;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct ImportOpenapiArgs {
+    /// OpenAPI 3 document, in JSON or YAML
+    file: PathBuf,
+
+    /// Router layout the handler stubs are generated for: standard, axum-controller, axum-folder-router
+    #[arg(long)]
+    router: Option<RouterStrategy>,
+
+    /// Config file to write; TOML, YAML or JSON by extension
+    #[arg(short, long, default_value = "rhupster.toml")]
+    output: PathBuf,
+
+    /// Overwrite the output file if it already exists
+    #[arg(long)]
+    force: bool,
+}
+
+pub fn run(args: ImportOpenapiArgs) -> Result<()> {
+    let mut import = openapi::import_file(&args.file)?;
+    for warning in &import.warnings {
+        println!("{} {}", style("warning").yellow().bold(), warning);
+    }
+    import.config.router_strategy = args.router;
+
+    let entities = import.config.entities.as_ref().map_or(0, Vec::len);
+    let operations = import.config.contract.as_ref().map_or(0, |c| c.operations.len());
+    let imported = format!("{} entity(ies) and {} operation(s)", entities, operations);
+    super::write_import(&import.config, &args.output, args.force, &imported)
+}
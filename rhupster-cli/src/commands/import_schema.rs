@@ -0,0 +1,34 @@
+use anyhow::Result;
+use clap::Args;
+use console::style;
+use rhupster_core::config::Database;
+use rhupster_core::schema_import;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct ImportSchemaArgs {
+    /// `.sql` file with CREATE TABLE / ALTER TABLE statements, or a SQLite database file
+    file: PathBuf,
+
+    /// SQL dialect of a `.sql` file: postgres, mysql, sqlite
+    #[arg(long, default_value = "postgres")]
+    dialect: Database,
+
+    /// Config file to write; TOML, YAML or JSON by extension
+    #[arg(short, long, default_value = "rhupster.toml")]
+    output: PathBuf,
+
+    /// Overwrite the output file if it already exists
+    #[arg(long)]
+    force: bool,
+}
+
+pub fn run(args: ImportSchemaArgs) -> Result<()> {
+    let import = schema_import::import_file(&args.file, Some(args.dialect))?;
+    for warning in &import.warnings {
+        println!("{} {}", style("warning").yellow().bold(), warning);
+    }
+
+    let entities = import.config.entities.as_ref().map_or(0, Vec::len);
+    super::write_import(&import.config, &args.output, args.force, &format!("{} entity(ies)", entities))
+}
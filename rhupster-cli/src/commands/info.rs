@@ -0,0 +1,49 @@
+use anyhow::Result;
+use console::style;
+use rhupster_core::config::{Authentication, ProjectConfig};
+use rhupster_core::manifest::GENERATOR_VERSION;
+
+pub fn run() -> Result<()> {
+    let (project_dir, manifest) = super::current_project()?;
+    let config = &manifest.config;
+
+    println!("{}", style(format!("Rhupster project '{}'", config.name)).bold().cyan());
+    println!("  Location:           {}", project_dir.display());
+    println!("  Generated by:       Rhupster v{}", manifest.generator_version);
+    println!("  Installed CLI:      Rhupster v{}", GENERATOR_VERSION);
+    println!("  Config schema:      v{}", manifest.schema_version);
+    if !manifest.templates.is_empty() {
+        println!("  Templates:          {}", manifest.templates);
+    }
+    for blueprint in &manifest.blueprints {
+        println!("  Blueprint:          {}", blueprint);
+    }
+
+    println!("\n{}", style("Configuration").bold());
+    print_config(config);
+    Ok(())
+}
+
+fn print_config(config: &ProjectConfig) {
+    let join = |items: Vec<String>| if items.is_empty() { "none".to_string() } else { items.join(", ") };
+
+    println!("  Database:           {}", config.database);
+    println!("  ORM:                {}", config.orm);
+    println!("  Infrastructure:     {}", join(config.infrastructure.iter().map(|i| i.to_string()).collect()));
+    println!("  Router strategy:    {}", config.router_strategy);
+    println!("  API docs UI:        {}", config.api_ui);
+    println!("  Frontend:           {}", config.frontend);
+    let auth = match &config.authentication {
+        Authentication::OAuth2(providers) => format!(
+            "OAuth2 ({})",
+            join(providers.iter().map(|p| p.to_string()).collect())
+        ),
+        other => format!("{:?}", other),
+    };
+    println!("  Authentication:     {}", auth);
+    println!("  HATEOAS:            {}", config.hateoas);
+    println!("  Docker Compose:     {}", config.devops.docker_compose);
+    println!("  AI agents:          {}", join(config.ai_agents.iter().map(|a| a.to_string()).collect()));
+    println!("  Primary key:        {}", config.primary_key);
+    println!("  Entities:           {}", join(config.entities.iter().map(|e| e.name.clone()).collect()));
+}
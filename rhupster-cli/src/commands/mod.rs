@@ -0,0 +1,104 @@
+pub mod diagram;
+pub mod entity;
+pub mod import_jdl;
+pub mod import_openapi;
+pub mod import_schema;
+pub mod info;
+pub mod new;
+pub mod regenerate;
+
+use anyhow::{anyhow, bail, Result};
+use console::style;
+use rhupster_core::config::{PartialProjectConfig, ProjectConfig};
+use rhupster_core::generator::{git, Blueprint, TemplateSource};
+use rhupster_core::manifest::{ProjectManifest, MANIFEST_FILE_NAME};
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::TEMPLATES;
+
+/// Fetches `git+<url>#<rev>` into the user cache directory. Otherwise prefers
+/// a local templates directory when it exists, then the templates built into the binary.
+pub fn template_source(templates: &str) -> Result<TemplateSource<'static>> {
+    if let Some((url, rev)) = git::parse_spec(templates) {
+        let source = git_source(url, rev)?;
+        println!("Using templates from: {}", source.describe());
+        return Ok(source);
+    }
+    let templates = Path::new(templates);
+    if templates.exists() {
+        println!("Using local templates from: {}", templates.display());
+        Ok(TemplateSource::Path(templates.to_path_buf()))
+    } else {
+        println!("Using embedded templates.");
+        Ok(TemplateSource::Embedded(&TEMPLATES))
+    }
+}
+
+/// Loads the blueprints at each directory or `git+<url>#<rev>`, in order.
+pub fn blueprints(specs: &[String]) -> Result<Vec<Blueprint<'static>>> {
+    let mut blueprints = Vec::new();
+    for spec in specs {
+        let source = match git::parse_spec(spec) {
+            Some((url, rev)) => git_source(url, rev)?,
+            None => TemplateSource::Path(PathBuf::from(spec)),
+        };
+        let blueprint = Blueprint::load(source)?;
+        println!("Using blueprint '{}' from: {}", blueprint.manifest.name, blueprint.templates.describe());
+        blueprints.push(blueprint);
+    }
+    Ok(blueprints)
+}
+
+/// Fetches a template repository into the user cache directory.
+fn git_source(url: &str, rev: &str) -> Result<TemplateSource<'static>> {
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| anyhow!("No cache directory to fetch templates into"))?
+        .join("rhupster")
+        .join("templates");
+    println!("Fetching templates from {} at {}...", url, rev);
+    TemplateSource::git(url, rev, &cache_dir)
+}
+
+/// Prints every validation warning and fails if the configuration cannot be generated.
+pub fn check_config(config: &ProjectConfig) -> Result<()> {
+    let report = config.validate();
+    for warning in &report.warnings {
+        println!("{} {}", style("warning").yellow().bold(), warning);
+    }
+    for error in &report.errors {
+        println!("{} {}", style("error").red().bold(), error);
+    }
+    if !report.is_ok() {
+        bail!("The project configuration has {} error(s); nothing was generated.", report.errors.len());
+    }
+    Ok(())
+}
+
+/// Writes an imported configuration to `output` once it validates, refusing to
+/// replace an existing file unless `force` is set. `imported` says what was
+/// imported, e.g. "3 entity(ies)".
+pub fn write_import(config: &PartialProjectConfig, output: &Path, force: bool, imported: &str) -> Result<()> {
+    check_config(&config.clone().into_config())?;
+    if output.exists() && !force {
+        bail!("{} already exists; pass --force to overwrite it", output.display());
+    }
+    config.to_file(output)?;
+
+    println!("{}", style(format!("Imported {} into {}.", imported, output.display())).green());
+    println!("Run `rhupster new --config {}` to generate the project.", output.display());
+    Ok(())
+}
+
+/// Loads the manifest of the Rhupster project in the current directory.
+pub fn current_project() -> Result<(PathBuf, ProjectManifest)> {
+    let project_dir = env::current_dir()?;
+    let manifest = ProjectManifest::load(&project_dir)?.ok_or_else(|| {
+        anyhow!(
+            "No {} found in {}. Run this command from the root of a project created with `rhupster new`.",
+            MANIFEST_FILE_NAME,
+            project_dir.display()
+        )
+    })?;
+    Ok((project_dir, manifest))
+}
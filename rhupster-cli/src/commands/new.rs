@@ -0,0 +1,176 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use console::style;
+use rhupster_core::config::PartialProjectConfig;
+use rhupster_core::generator::{Generator, ZipWriter};
+use rhupster_core::manifest::{ProjectManifest, MANIFEST_FILE_NAME};
+use rhupster_core::presets::Presets;
+use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+use super::regenerate::{sources, RegenerateArgs};
+use crate::conflicts::{self, ConflictArgs};
+use crate::flags::ConfigFlags;
+use crate::preview::{DryRunArgs, Preview};
+use crate::prompts::PromptService;
+
+#[derive(Args, Debug)]
+pub struct NewArgs {
+    /// Output directory for the new project
+    #[arg(short, long, default_value = ".")]
+    output: PathBuf,
+
+    /// Write the project into this zip archive, in a folder named after the
+    /// output directory, instead of onto the disk
+    #[arg(long, value_name = "FILE", conflicts_with = "dry_run")]
+    zip: Option<PathBuf>,
+
+    /// Templates directory, or a git repository as git+<url>#<rev> (defaults to
+    /// those of a reused saved configuration, then ./templates)
+    #[arg(short, long)]
+    templates: Option<String>,
+
+    /// Blueprint directory or git+<url>#<rev> to layer over the templates; repeat to stack
+    /// several (defaults to those of a reused saved configuration)
+    #[arg(short, long = "blueprint")]
+    blueprints: Vec<String>,
+
+    /// Project config file (TOML, YAML or JSON); skips the interactive wizard
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Start from a named preset (minimal, monolith, event-driven or one of your own)
+    #[arg(short, long, env = "RHUPSTER_PRESET")]
+    preset: Option<String>,
+
+    /// File with user-defined presets (defaults to <config dir>/rhupster/presets.toml)
+    #[arg(long, env = "RHUPSTER_PRESETS_FILE")]
+    presets_file: Option<PathBuf>,
+
+    /// Accept the default for every question not answered by a flag
+    #[arg(short, long, env = "RHUPSTER_YES")]
+    yes: bool,
+
+    #[command(flatten)]
+    answers: ConfigFlags,
+
+    #[command(flatten)]
+    preview: DryRunArgs,
+
+    #[command(flatten)]
+    conflicts: ConflictArgs,
+}
+
+pub async fn run(args: NewArgs) -> Result<()> {
+    println!("{}", style("Welcome to Rhupster - The Rust/Axum Enterprise Generator").bold().cyan());
+
+    let current_dir = env::current_dir()?;
+    let existing_project = ProjectManifest::load(&current_dir)?;
+    let prompts = PromptService::new();
+    let mut reused = None;
+
+    let flags = args.answers.into_partial()?;
+    let preset = match &args.preset {
+        Some(name) => {
+            let presets_file = args.presets_file.clone().or_else(default_presets_file);
+            let preset = Presets::load(presets_file.as_deref())?.get(name)?.clone();
+            println!("Using preset: {}", name);
+            preset
+        }
+        None => PartialProjectConfig::default(),
+    };
+    let answers = preset.clone().merge(flags.clone());
+
+    let config = match (&args.config, existing_project) {
+        (Some(path), _) => {
+            println!("Loading configuration from: {}", path.display());
+            preset
+                .merge(PartialProjectConfig::from_file(path)?)
+                .merge(flags)
+                .into_config()
+        }
+        (None, Some(manifest)) if !args.yes => {
+            println!(
+                "Found {} (generated by Rhupster v{}).",
+                MANIFEST_FILE_NAME, manifest.generator_version
+            );
+            if prompts.confirm_reuse_saved_config()? {
+                let config = answers.apply_to(manifest.config.clone());
+                reused = Some(manifest);
+                config
+            } else {
+                println!("Let's configure your new project.\n");
+                prompts.collect_config(answers)?
+            }
+        }
+        _ if args.yes => answers.into_config(),
+        _ => {
+            println!("Let's configure your new project.\n");
+            prompts.collect_config(answers)?
+        }
+    };
+
+    // Reusing the saved configuration in place is a regeneration of the project
+    let reused = match reused {
+        Some(mut manifest) if args.output.to_string_lossy() == "." => {
+            manifest.config = config;
+            let regenerate = RegenerateArgs::new(args.templates, args.blueprints, args.preview, args.conflicts);
+            return super::regenerate::regenerate(&current_dir, manifest, regenerate).await;
+        }
+        reused => reused,
+    };
+
+    super::check_config(&config)?;
+
+    println!("\n{}", style("Configuration Complete!").green());
+    println!("Generating project '{}'...", config.name);
+    println!("  - Database: {}", config.database);
+    println!("  - ORM: {}", config.orm);
+    println!("  - Auth: {:?}", config.authentication);
+    if !config.entities.is_empty() {
+        println!("  - Entities: {}", config.entities.iter().map(|e| e.name.as_str()).collect::<Vec<_>>().join(", "));
+    }
+
+    // Determine output directory
+    let output_path = if args.output.to_string_lossy() == "." {
+        current_dir.join(&config.name)
+    } else {
+        args.output
+    };
+
+    let (template_source, blueprints) = match &reused {
+        Some(manifest) => sources(manifest, args.templates, args.blueprints)?,
+        None => (
+            super::template_source(args.templates.as_deref().unwrap_or("templates"))?,
+            super::blueprints(&args.blueprints)?,
+        ),
+    };
+
+    let generator = Generator::new(config, template_source).with_blueprints(blueprints);
+    if args.preview.dry_run {
+        generator.render_into(&output_path, &mut Preview::new(&output_path, &args.preview)).await?;
+        return Ok(());
+    }
+    if let Some(zip) = &args.zip {
+        let archive = File::create(zip).with_context(|| format!("Failed to create {}", zip.display()))?;
+        let base = output_path.parent().unwrap_or(&output_path);
+        generator.render_into(&output_path, &mut ZipWriter::new(BufWriter::new(archive), base)).await?.flush()?;
+        println!("\n{}", style(format!("Success! Project archived to {}.", zip.display())).bold().green());
+        return Ok(());
+    }
+    let mut resolver = args.conflicts.resolver(&output_path);
+    let summary = generator.generate_with(&output_path, resolver.as_mut()).await.map_err(conflicts::hint)?;
+    conflicts::print_summary(&summary, &output_path);
+
+    println!("\n{}", style("Success! Project generated.").bold().green());
+    println!("cd {}", output_path.display());
+    println!("cargo run");
+
+    Ok(())
+}
+
+fn default_presets_file() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("rhupster").join("presets.toml"))
+}
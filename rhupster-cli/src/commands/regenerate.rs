@@ -0,0 +1,79 @@
+use anyhow::Result;
+use clap::Args;
+use console::style;
+use rhupster_core::generator::{Blueprint, Generator, TemplateSource};
+use rhupster_core::manifest::ProjectManifest;
+use std::path::Path;
+
+use crate::conflicts::{self, ConflictArgs};
+use crate::preview::{DryRunArgs, Preview};
+
+#[derive(Args, Debug)]
+pub struct RegenerateArgs {
+    /// Templates directory, or a git repository as git+<url>#<rev> (defaults to
+    /// the templates the project was generated from, then ./templates)
+    #[arg(short, long)]
+    templates: Option<String>,
+
+    /// Blueprint directory or git+<url>#<rev> to layer over the templates; repeat
+    /// to stack several (defaults to the blueprints the project was generated with)
+    #[arg(short, long = "blueprint")]
+    blueprints: Vec<String>,
+
+    #[command(flatten)]
+    preview: DryRunArgs,
+
+    #[command(flatten)]
+    conflicts: ConflictArgs,
+}
+
+impl RegenerateArgs {
+    pub(super) fn new(templates: Option<String>, blueprints: Vec<String>, preview: DryRunArgs, conflicts: ConflictArgs) -> Self {
+        Self { templates, blueprints, preview, conflicts }
+    }
+}
+
+pub async fn run(args: RegenerateArgs) -> Result<()> {
+    let (project_dir, manifest) = super::current_project()?;
+    regenerate(&project_dir, manifest, args).await
+}
+
+/// Renders the project at `project_dir` again from the configuration in `manifest`.
+pub(super) async fn regenerate(project_dir: &Path, manifest: ProjectManifest, args: RegenerateArgs) -> Result<()> {
+    println!(
+        "Regenerating '{}' from its saved configuration (generated by Rhupster v{})...",
+        manifest.config.name, manifest.generator_version
+    );
+
+    super::check_config(&manifest.config)?;
+
+    let (template_source, blueprints) = sources(&manifest, args.templates, args.blueprints)?;
+    let generator = Generator::new(manifest.config, template_source)
+        .with_blueprints(blueprints)
+        .with_schema_history(manifest.schema);
+    if args.preview.dry_run {
+        generator.render_into(project_dir, &mut Preview::new(project_dir, &args.preview)).await?;
+        return Ok(());
+    }
+    let mut resolver = args.conflicts.resolver(project_dir);
+    let summary = generator.generate_with(project_dir, resolver.as_mut()).await.map_err(conflicts::hint)?;
+    conflicts::print_summary(&summary, project_dir);
+
+    println!("\n{}", style("Success! Project regenerated.").bold().green());
+    Ok(())
+}
+
+/// The templates and blueprints to render the project of `manifest` with: the
+/// given ones, or else those it was generated from.
+pub(super) fn sources(
+    manifest: &ProjectManifest,
+    templates: Option<String>,
+    blueprints: Vec<String>,
+) -> Result<(TemplateSource<'static>, Vec<Blueprint<'static>>)> {
+    let templates = templates.unwrap_or_else(|| match manifest.templates.as_str() {
+        "" => "templates".to_string(),
+        recorded => recorded.to_string(),
+    });
+    let blueprints = if blueprints.is_empty() { manifest.blueprints.clone() } else { blueprints };
+    Ok((super::template_source(&templates)?, super::blueprints(&blueprints)?))
+}
@@ -0,0 +1,110 @@
+use anyhow::Result;
+use clap::Args;
+use console::style;
+use dialoguer::{theme::ColorfulTheme, Select};
+use rhupster_core::generator::{ConflictError, ConflictPolicy, ConflictResolver, Resolution, WriteSummary};
+use std::io::IsTerminal;
+use std::path::Path;
+
+use crate::preview;
+
+#[derive(Args, Debug)]
+pub struct ConflictArgs {
+    /// Overwrite files that were changed since they were generated
+    #[arg(long, conflicts_with = "skip_existing")]
+    pub force: bool,
+
+    /// Keep files that were changed since they were generated
+    #[arg(long)]
+    pub skip_existing: bool,
+}
+
+impl ConflictArgs {
+    /// `--force` and `--skip-existing` win; otherwise ask per file on a terminal and refuse elsewhere.
+    pub fn resolver(&self, root: &Path) -> Box<dyn ConflictResolver> {
+        if self.force {
+            Box::new(ConflictPolicy::Overwrite)
+        } else if self.skip_existing {
+            Box::new(ConflictPolicy::Skip)
+        } else if std::io::stdin().is_terminal() {
+            Box::new(InteractiveResolver::new(root))
+        } else {
+            Box::new(ConflictPolicy::Fail)
+        }
+    }
+}
+
+/// Asks, for each modified file, whether to overwrite it, keep it or look at the diff first.
+pub struct InteractiveResolver {
+    theme: ColorfulTheme,
+    root: std::path::PathBuf,
+    overwrite_all: bool,
+}
+
+impl InteractiveResolver {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            theme: ColorfulTheme::default(),
+            root: root.to_path_buf(),
+            overwrite_all: false,
+        }
+    }
+}
+
+impl ConflictResolver for InteractiveResolver {
+    fn resolve(&mut self, path: &Path, existing: &[u8], generated: &[u8]) -> Result<Resolution> {
+        if self.overwrite_all {
+            return Ok(Resolution::Overwrite);
+        }
+
+        let relative = path.strip_prefix(&self.root).unwrap_or(path).display().to_string();
+        let choices = [
+            "Overwrite",
+            "Skip (keep my version)",
+            "Show diff",
+            "Overwrite this and all remaining files",
+            "Abort",
+        ];
+        loop {
+            let idx = Select::with_theme(&self.theme)
+                .with_prompt(format!("{} was modified. What should be done?", relative))
+                .default(1)
+                .items(&choices)
+                .interact()?;
+            match idx {
+                0 => return Ok(Resolution::Overwrite),
+                1 => return Ok(Resolution::Skip),
+                2 => preview::print_file_diff(&relative, existing, generated),
+                3 => {
+                    self.overwrite_all = true;
+                    return Ok(Resolution::Overwrite);
+                }
+                4 => return Ok(Resolution::Abort),
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+/// Adds the flags that resolve a refused conflict to its error.
+pub fn hint(err: anyhow::Error) -> anyhow::Error {
+    match err.downcast_ref::<ConflictError>() {
+        Some(conflict) => anyhow::anyhow!("{}. Overwrite it with --force or keep it with --skip-existing.", conflict),
+        None => err,
+    }
+}
+
+/// Prints how many files were created, overwritten, kept and left untouched.
+pub fn print_summary(summary: &WriteSummary, root: &Path) {
+    for path in &summary.skipped {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        println!("{} {} (kept existing file)", style("skipped").yellow(), relative.display());
+    }
+    println!(
+        "{} created, {} overwritten, {} skipped, {} unchanged.",
+        summary.created.len(),
+        summary.overwritten.len(),
+        summary.skipped.len(),
+        summary.unchanged.len()
+    );
+}
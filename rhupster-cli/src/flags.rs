@@ -0,0 +1,99 @@
+use anyhow::Result;
+use clap::Args;
+use rhupster_core::config::{
+    AIAgent, ApiUi, Authentication, Database, DevOps, Frontend, Infrastructure, OAuthProvider, Orm,
+    PartialProjectConfig, RouterStrategy,
+};
+use rhupster_core::model::PrimaryKey;
+use std::str::FromStr;
+
+/// One flag per wizard question. Each can also be set through a `RHUPSTER_*` environment variable.
+#[derive(Args, Debug, Default)]
+pub struct ConfigFlags {
+    /// Project name
+    #[arg(long, env = "RHUPSTER_NAME")]
+    name: Option<String>,
+
+    /// Database: postgres, mysql, mongodb, sqlite
+    #[arg(long, env = "RHUPSTER_DATABASE")]
+    database: Option<Database>,
+
+    /// ORM: sqlx, diesel, seaorm, none
+    #[arg(long, env = "RHUPSTER_ORM")]
+    orm: Option<Orm>,
+
+    /// Comma-separated infrastructure: redis, kafka, socket (or `none`)
+    #[arg(long, env = "RHUPSTER_INFRA", value_delimiter = ',')]
+    infra: Option<Vec<String>>,
+
+    /// Router strategy: standard, axum-controller, axum-folder-router
+    #[arg(long, env = "RHUPSTER_ROUTER")]
+    router: Option<RouterStrategy>,
+
+    /// API documentation UI: swagger, scalar, none
+    #[arg(long, env = "RHUPSTER_API_UI")]
+    api_ui: Option<ApiUi>,
+
+    /// Frontend: react, vue, svelte, angular, none
+    #[arg(long, env = "RHUPSTER_FRONTEND")]
+    frontend: Option<Frontend>,
+
+    /// Authentication: none, basic, jwt, oauth2
+    #[arg(long, env = "RHUPSTER_AUTH")]
+    auth: Option<Authentication>,
+
+    /// Comma-separated OAuth2 providers: discord, google, apple, github (implies --auth oauth2; an error with any other --auth)
+    #[arg(long, env = "RHUPSTER_OAUTH_PROVIDERS", value_delimiter = ',')]
+    oauth_providers: Option<Vec<OAuthProvider>>,
+
+    /// Enable HATEOAS support (`--hateoas=false` to answer no)
+    #[arg(long, env = "RHUPSTER_HATEOAS", num_args = 0..=1, default_missing_value = "true")]
+    hateoas: Option<bool>,
+
+    /// Generate Docker Compose (`--docker-compose=false` to answer no)
+    #[arg(long, env = "RHUPSTER_DOCKER_COMPOSE", num_args = 0..=1, default_missing_value = "true")]
+    docker_compose: Option<bool>,
+
+    /// Id type of users and entities: u64, i64, uuid-v4, uuid-v7, ulid
+    #[arg(long, env = "RHUPSTER_PRIMARY_KEY")]
+    primary_key: Option<PrimaryKey>,
+
+    /// Comma-separated AI agent folders: claude, gemini, gpt (or `none`)
+    #[arg(long, env = "RHUPSTER_AI_AGENTS", value_delimiter = ',')]
+    ai_agents: Option<Vec<String>>,
+}
+
+impl ConfigFlags {
+    pub fn into_partial(self) -> Result<PartialProjectConfig> {
+        let authentication = Authentication::with_providers(self.auth, self.oauth_providers)?;
+
+        Ok(PartialProjectConfig {
+            name: self.name,
+            database: self.database,
+            orm: self.orm,
+            infrastructure: self.infra.map(|values| parse_list::<Infrastructure>(&values)).transpose()?,
+            frontend: self.frontend,
+            authentication,
+            devops: self.docker_compose.map(|docker_compose| DevOps { docker_compose }),
+            router_strategy: self.router,
+            api_ui: self.api_ui,
+            hateoas: self.hateoas,
+            ai_agents: self.ai_agents.map(|values| parse_list::<AIAgent>(&values)).transpose()?,
+            primary_key: self.primary_key,
+            entities: None,
+            enums: None,
+            relationships: None,
+            contract: None,
+        })
+    }
+}
+
+/// Parses a comma-separated list where a lone `none` (or an empty value) means "nothing selected".
+fn parse_list<T: FromStr<Err = anyhow::Error>>(values: &[String]) -> Result<Vec<T>> {
+    values
+        .iter()
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty() && !v.eq_ignore_ascii_case("none"))
+        .map(T::from_str)
+        .collect()
+}
@@ -1,66 +1,54 @@
 use anyhow::Result;
-use clap::Parser;
-use console::style;
-use rhupster_core::generator::{Generator, TemplateSource};
-use std::env;
-use std::path::PathBuf;
+use clap::{Parser, Subcommand};
 use include_dir::{include_dir, Dir};
 
+mod commands;
+mod conflicts;
+mod flags;
+mod preview;
 mod prompts;
-use prompts::PromptService;
 
 static TEMPLATES: Dir = include_dir!("$CARGO_MANIFEST_DIR/../templates");
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Output directory for the new project
-    #[arg(short, long, default_value = ".")]
-    output: PathBuf,
+#[command(name = "rhupster", author, version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
 
-    /// Path to the templates directory (defaults to ./templates)
-    #[arg(short, long, default_value = "templates")]
-    templates: PathBuf,
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Create a new project with the interactive wizard
+    New(Box<commands::new::NewArgs>),
+    /// Add a domain entity to the project in the current directory
+    Entity(commands::entity::EntityArgs),
+    /// Re-render the project in the current directory from its saved configuration
+    Regenerate(commands::regenerate::RegenerateArgs),
+    /// Print the saved configuration and versions of the project in the current directory
+    Info,
+    /// Convert a JHipster JDL file into a Rhupster config file
+    ImportJdl(commands::import_jdl::ImportJdlArgs),
+    /// Infer entities and relationships from SQL DDL or a SQLite database
+    ImportSchema(commands::import_schema::ImportSchemaArgs),
+    /// Import entities and handler stubs from an OpenAPI 3 document
+    ImportOpenapi(commands::import_openapi::ImportOpenapiArgs),
+    /// Draw the entity relationship diagram of a domain file as Mermaid or PlantUML
+    Diagram(commands::diagram::DiagramArgs),
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let args = Args::parse();
-
-    println!("{}", style("Welcome to Rhupster - The Rust/Axum Enterprise Generator").bold().cyan());
-    println!("Let's configure your new project.\n");
-
-    let prompts = PromptService::new();
-    let config = prompts.collect_config()?;
-
-    println!("\n{}", style("Configuration Complete!").green());
-    println!("Generating project '{}'...", config.name);
-    println!("  - Database: {}", config.database);
-    println!("  - ORM: {}", config.orm);
-    println!("  - Auth: {:?}", config.authentication);
-    println!("  - Domain: Trucks & Products included.");
-
-    // Determine output directory
-    let output_path = if args.output.to_string_lossy() == "." {
-        env::current_dir()?.join(&config.name)
-    } else {
-        args.output
-    };
-
-    let template_source = if args.templates.exists() {
-        println!("Using local templates from: {}", args.templates.display());
-        TemplateSource::Path(args.templates)
-    } else {
-        println!("Using embedded templates.");
-        TemplateSource::Embedded(&TEMPLATES)
-    };
-
-    let generator = Generator::new(config, template_source);
-    generator.generate(&output_path).await?;
-
-    println!("\n{}", style("Success! Project generated.").bold().green());
-    println!("cd {}", output_path.display());
-    println!("cargo run");
-
-    Ok(())
-}
\ No newline at end of file
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::New(args) => commands::new::run(*args).await,
+        Command::Entity(args) => commands::entity::run(args),
+        Command::Regenerate(args) => commands::regenerate::run(args).await,
+        Command::Info => commands::info::run(),
+        Command::ImportJdl(args) => commands::import_jdl::run(args),
+        Command::ImportSchema(args) => commands::import_schema::run(args),
+        Command::ImportOpenapi(args) => commands::import_openapi::run(args),
+        Command::Diagram(args) => commands::diagram::run(args),
+    }
+}
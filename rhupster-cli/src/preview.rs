@@ -0,0 +1,174 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use console::style;
+use rhupster_core::generator::{OutputSink, OutputTree};
+use similar::TextDiff;
+use std::io::ErrorKind;
+use std::path::Path;
+
+#[derive(Args, Debug)]
+pub struct DryRunArgs {
+    /// Render everything and print the file tree without writing to disk
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// With --dry-run, also print the full rendered contents of every file
+    #[arg(long, requires = "dry_run", conflicts_with = "diff")]
+    pub show_contents: bool,
+
+    /// With --dry-run, print a unified diff against the files already on disk
+    #[arg(long, requires = "dry_run")]
+    pub diff: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FileStatus {
+    New,
+    Modified,
+    Unchanged,
+}
+
+/// Contents of `path` on disk, or `None` if there is no such file yet.
+fn read_existing(path: &Path) -> Result<Option<Vec<u8>>> {
+    match std::fs::read(path) {
+        Ok(existing) => Ok(Some(existing)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+fn status_of(path: &Path, contents: &[u8]) -> Result<FileStatus> {
+    Ok(match read_existing(path)? {
+        Some(existing) if existing == contents => FileStatus::Unchanged,
+        Some(_) => FileStatus::Modified,
+        None => FileStatus::New,
+    })
+}
+
+/// Prints what a generation run would produce under `root` instead of writing it,
+/// as requested by `args`.
+pub struct Preview<'a> {
+    root: &'a Path,
+    args: &'a DryRunArgs,
+}
+
+impl<'a> Preview<'a> {
+    pub fn new(root: &'a Path, args: &'a DryRunArgs) -> Self {
+        Self { root, args }
+    }
+}
+
+impl OutputSink for Preview<'_> {
+    type Output = ();
+
+    fn consume(&mut self, files: &OutputTree) -> Result<()> {
+        print(files, self.root, self.args)
+    }
+}
+
+fn print(files: &OutputTree, root: &Path, args: &DryRunArgs) -> Result<()> {
+    println!("\n{}", style(format!("{}/", root.display())).bold());
+
+    let mut previous: Vec<String> = Vec::new();
+    let (mut new, mut modified, mut unchanged) = (0, 0, 0);
+
+    for (path, contents) in files.iter() {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let components: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let shared = previous
+            .iter()
+            .zip(&components)
+            .take_while(|(a, b)| a == b)
+            .count()
+            .min(components.len() - 1);
+
+        for (depth, component) in components.iter().enumerate().skip(shared) {
+            let indent = "    ".repeat(depth + 1);
+            if depth + 1 < components.len() {
+                println!("{}{}/", indent, component);
+                continue;
+            }
+            let label = match status_of(path, contents)? {
+                FileStatus::New => {
+                    new += 1;
+                    style("new").green()
+                }
+                FileStatus::Modified => {
+                    modified += 1;
+                    style("modified").yellow()
+                }
+                FileStatus::Unchanged => {
+                    unchanged += 1;
+                    style("unchanged").dim()
+                }
+            };
+            println!("{}{} ({})", indent, component, label);
+        }
+        previous = components;
+    }
+
+    if args.show_contents {
+        print_contents(files, root);
+    } else if args.diff {
+        print_diff(files, root)?;
+    }
+
+    println!(
+        "\n{} files: {} new, {} modified, {} unchanged. Nothing was written (dry run).",
+        files.len(),
+        new,
+        modified,
+        unchanged
+    );
+    Ok(())
+}
+
+fn print_contents(files: &OutputTree, root: &Path) {
+    for (path, file) in files.files() {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        println!("\n{}", style(format!("==> {} <==", relative.display())).bold().cyan());
+        if let Some(template) = &file.template {
+            println!("{}", style(format!("(from {})", template.display())).dim());
+        }
+        println!("{}", String::from_utf8_lossy(&file.contents));
+    }
+}
+
+fn print_diff(files: &OutputTree, root: &Path) -> Result<()> {
+    for (path, contents) in files.iter() {
+        let existing = read_existing(path)?.unwrap_or_default();
+        if existing == *contents {
+            continue;
+        }
+        let relative = path.strip_prefix(root).unwrap_or(path).display().to_string();
+        print_file_diff(&relative, &existing, contents);
+    }
+    Ok(())
+}
+
+/// Prints a coloured unified diff between what is on disk and what would be written.
+pub fn print_file_diff(relative: &str, existing: &[u8], generated: &[u8]) {
+    let old = String::from_utf8_lossy(existing);
+    let new = String::from_utf8_lossy(generated);
+    let diff = TextDiff::from_lines(old.as_ref(), new.as_ref());
+
+    println!();
+    for line in diff
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", relative), &format!("b/{}", relative))
+        .to_string()
+        .lines()
+    {
+        let styled = match line.chars().next() {
+            Some('+') => style(line).green(),
+            Some('-') => style(line).red(),
+            Some('@') => style(line).cyan(),
+            _ => style(line),
+        };
+        println!("{}", styled);
+    }
+}
@@ -2,32 +2,44 @@ use anyhow::Result;
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect, Select};
 use rhupster_core::config::{
     AIAgent, ApiUi, Authentication, Database, DevOps, Frontend, Infrastructure, OAuthProvider, Orm,
-    ProjectConfig, RouterStrategy,
+    PartialProjectConfig, ProjectConfig, RouterStrategy,
 };
+use rhupster_core::model::{Field, FieldType, PrimaryKey};
 
 pub struct PromptService {
     theme: ColorfulTheme,
+    defaults: ProjectConfig,
 }
 
 impl PromptService {
     pub fn new() -> Self {
         Self {
             theme: ColorfulTheme::default(),
+            defaults: ProjectConfig::default(),
         }
     }
 
-    pub fn collect_config(&self) -> Result<ProjectConfig> {
-        let name = self.ask_name()?;
-        let database = self.ask_database()?;
-        let orm = self.ask_orm(database)?;
-        let infrastructure = self.ask_infrastructure()?;
-        let router_strategy = self.ask_router_strategy()?;
-        let api_ui = self.ask_api_ui()?;
-        let frontend = self.ask_frontend()?;
-        let authentication = self.ask_authentication()?;
-        let hateoas = self.ask_hateoas()?;
-        let docker_compose = self.ask_docker_compose()?;
-        let ai_agents = self.ask_ai_agents()?;
+    /// Asks every question that `answers` leaves open; answered ones are skipped.
+    pub fn collect_config(&self, answers: PartialProjectConfig) -> Result<ProjectConfig> {
+        let name = answers.name.map_or_else(|| self.ask_name(), Ok)?;
+        let database = answers.database.map_or_else(|| self.ask_database(), Ok)?;
+        let orm = answers.orm.map_or_else(|| self.ask_orm(database), Ok)?;
+        let infrastructure = answers.infrastructure.map_or_else(|| self.ask_infrastructure(), Ok)?;
+        let router_strategy = answers.router_strategy.map_or_else(|| self.ask_router_strategy(), Ok)?;
+        let api_ui = answers.api_ui.map_or_else(|| self.ask_api_ui(), Ok)?;
+        let frontend = answers.frontend.map_or_else(|| self.ask_frontend(), Ok)?;
+        let authentication = match answers.authentication {
+            Some(Authentication::OAuth2(providers)) if providers.is_empty() => {
+                Authentication::OAuth2(self.ask_oauth_providers()?)
+            }
+            Some(authentication) => authentication,
+            None => self.ask_authentication()?,
+        };
+        let hateoas = answers.hateoas.map_or_else(|| self.ask_hateoas(), Ok)?;
+        let devops = answers
+            .devops
+            .map_or_else(|| self.ask_docker_compose().map(|docker_compose| DevOps { docker_compose }), Ok)?;
+        let ai_agents = answers.ai_agents.map_or_else(|| self.ask_ai_agents(), Ok)?;
 
         Ok(ProjectConfig {
             name,
@@ -36,18 +48,59 @@ impl PromptService {
             infrastructure,
             frontend,
             authentication,
-            devops: DevOps { docker_compose },
+            devops,
             router_strategy,
             api_ui,
             hateoas,
             ai_agents,
+            primary_key: answers.primary_key.unwrap_or(if database == Database::MongoDB { PrimaryKey::UuidV7 } else { PrimaryKey::default() }),
+            entities: answers.entities.unwrap_or_else(|| self.defaults.entities.clone()),
+            enums: answers.enums.unwrap_or_default(),
+            relationships: answers.relationships.unwrap_or_default(),
+            contract: answers.contract.unwrap_or_default(),
         })
     }
 
+    pub fn collect_entity_fields(&self, entity: &str) -> Result<Vec<Field>> {
+        let mut fields = Vec::new();
+        loop {
+            let name: String = Input::with_theme(&self.theme)
+                .with_prompt(format!("Field name for {} (leave empty to finish)", entity))
+                .allow_empty(true)
+                .interact_text()?;
+            if name.is_empty() {
+                return Ok(fields);
+            }
+
+            let idx = Select::with_theme(&self.theme)
+                .with_prompt(format!("Type of '{}'", name))
+                .default(0)
+                .items(&FieldType::ALL)
+                .interact()?;
+            let required = Confirm::with_theme(&self.theme)
+                .with_prompt(format!("Is '{}' required?", name))
+                .default(true)
+                .interact()?;
+
+            fields.push(Field {
+                required,
+                ..Field::new(name, FieldType::ALL[idx].clone())
+            });
+        }
+    }
+
+    pub fn confirm_reuse_saved_config(&self) -> Result<bool> {
+        Confirm::with_theme(&self.theme)
+            .with_prompt("This directory is an existing Rhupster project. Regenerate it with the saved configuration?")
+            .default(true)
+            .interact()
+            .map_err(Into::into)
+    }
+
     fn ask_name(&self) -> Result<String> {
         Input::with_theme(&self.theme)
             .with_prompt("What is your project name?")
-            .default("my-axum-app".into())
+            .default(self.defaults.name.clone())
             .interact_text()
             .map_err(Into::into)
     }
@@ -61,7 +114,7 @@ impl PromptService {
         ];
         let idx = Select::with_theme(&self.theme)
             .with_prompt("Select a Database")
-            .default(0)
+            .default(default_index(&db_opts, &self.defaults.database))
             .items(&db_opts)
             .interact()?;
         Ok(db_opts[idx])
@@ -75,7 +128,7 @@ impl PromptService {
             let orm_opts = vec![Orm::Sqlx, Orm::Diesel, Orm::SeaOrm]; // Added SeaOrm
             let idx = Select::with_theme(&self.theme)
                 .with_prompt("Select an ORM")
-                .default(0)
+                .default(default_index(&orm_opts, &self.defaults.orm))
                 .items(&orm_opts)
                 .interact()?;
             Ok(orm_opts[idx])
@@ -103,7 +156,7 @@ impl PromptService {
         ]; // Reintroduced AxumFolderRouter
         let idx = Select::with_theme(&self.theme)
             .with_prompt("Select Routing Strategy")
-            .default(0)
+            .default(default_index(&opts, &self.defaults.router_strategy))
             .items(&opts)
             .interact()?;
         Ok(opts[idx])
@@ -113,7 +166,7 @@ impl PromptService {
         let opts = vec![ApiUi::Swagger, ApiUi::Scalar, ApiUi::None];
         let idx = Select::with_theme(&self.theme)
             .with_prompt("Select API Documentation UI")
-            .default(0)
+            .default(default_index(&opts, &self.defaults.api_ui))
             .items(&opts)
             .interact()?;
         Ok(opts[idx])
@@ -129,7 +182,7 @@ impl PromptService {
         ];
         let idx = Select::with_theme(&self.theme)
             .with_prompt("Select a Frontend Framework")
-            .default(0)
+            .default(default_index(&opts, &self.defaults.frontend))
             .items(&opts)
             .interact()?;
         Ok(opts[idx])
@@ -137,9 +190,15 @@ impl PromptService {
 
     fn ask_authentication(&self) -> Result<Authentication> {
         let types = vec!["None", "Basic", "JWT (User/Role System)", "OAuth2"];
+        let default = match self.defaults.authentication {
+            Authentication::None => 0,
+            Authentication::Basic => 1,
+            Authentication::Jwt => 2,
+            Authentication::OAuth2(_) => 3,
+        };
         let idx = Select::with_theme(&self.theme)
             .with_prompt("Select Authentication Strategy")
-            .default(2)
+            .default(default)
             .items(&types)
             .interact()?;
 
@@ -147,29 +206,29 @@ impl PromptService {
             0 => Ok(Authentication::None),
             1 => Ok(Authentication::Basic),
             2 => Ok(Authentication::Jwt),
-            3 => {
-                let opts = vec![
-                    OAuthProvider::Discord,
-                    OAuthProvider::Google,
-                    OAuthProvider::Apple,
-                    OAuthProvider::GitHub,
-                ];
-                let idxs = MultiSelect::with_theme(&self.theme)
-                    .with_prompt("Select OAuth2 Providers")
-                    .items(&opts)
-                    .interact()?;
-                Ok(Authentication::OAuth2(
-                    idxs.iter().map(|&i| opts[i]).collect(),
-                ))
-            }
+            3 => Ok(Authentication::OAuth2(self.ask_oauth_providers()?)),
             _ => unreachable!(),
         }
     }
 
+    fn ask_oauth_providers(&self) -> Result<Vec<OAuthProvider>> {
+        let opts = vec![
+            OAuthProvider::Discord,
+            OAuthProvider::Google,
+            OAuthProvider::Apple,
+            OAuthProvider::GitHub,
+        ];
+        let idxs = MultiSelect::with_theme(&self.theme)
+            .with_prompt("Select OAuth2 Providers")
+            .items(&opts)
+            .interact()?;
+        Ok(idxs.iter().map(|&i| opts[i]).collect())
+    }
+
     fn ask_hateoas(&self) -> Result<bool> {
         Confirm::with_theme(&self.theme)
             .with_prompt("Enable HATEOAS (Hypermedia) support?")
-            .default(false)
+            .default(self.defaults.hateoas)
             .interact()
             .map_err(Into::into)
     }
@@ -177,7 +236,7 @@ impl PromptService {
     fn ask_docker_compose(&self) -> Result<bool> {
         Confirm::with_theme(&self.theme)
             .with_prompt("Generate Docker Compose?")
-            .default(true)
+            .default(self.defaults.devops.docker_compose)
             .interact()
             .map_err(Into::into)
     }
@@ -191,3 +250,7 @@ impl PromptService {
         Ok(idxs.iter().map(|&i| opts[i]).collect())
     }
 }
+
+fn default_index<T: PartialEq>(opts: &[T], default: &T) -> usize {
+    opts.iter().position(|o| o == default).unwrap_or(0)
+}
@@ -1,6 +1,11 @@
+use crate::model::{ApiContract, DomainModel, Entity, EnumDef, Field, FieldType, PrimaryKey, Relationship};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ProjectConfig {
     pub name: String,
     pub database: Database,
@@ -13,6 +18,245 @@ pub struct ProjectConfig {
     pub api_ui: ApiUi,
     pub hateoas: bool,
     pub ai_agents: Vec<AIAgent>,
+    /// Id type of the users table and of every entity that doesn't pick its own.
+    pub primary_key: PrimaryKey,
+    pub entities: Vec<Entity>,
+    pub enums: Vec<EnumDef>,
+    pub relationships: Vec<Relationship>,
+    /// Schemas and operations imported from an OpenAPI document.
+    pub contract: ApiContract,
+}
+
+impl Default for ProjectConfig {
+    /// The answers the interactive wizard pre-selects.
+    fn default() -> Self {
+        Self {
+            name: "my-axum-app".to_string(),
+            database: Database::Postgres,
+            orm: Orm::Sqlx,
+            infrastructure: Vec::new(),
+            frontend: Frontend::React,
+            authentication: Authentication::Jwt,
+            devops: DevOps::default(),
+            router_strategy: RouterStrategy::Standard,
+            api_ui: ApiUi::Swagger,
+            hateoas: false,
+            ai_agents: Vec::new(),
+            primary_key: PrimaryKey::default(),
+            entities: vec![example_entity()],
+            enums: Vec::new(),
+            relationships: Vec::new(),
+            contract: ApiContract::default(),
+        }
+    }
+}
+
+/// The `Truck` slice new projects start with, so there is one working example
+/// of every layer to copy from.
+fn example_entity() -> Entity {
+    Entity {
+        name: "Truck".to_string(),
+        fields: vec![
+            Field {
+                required: true,
+                min: Some(3),
+                max: Some(50),
+                ..Field::new("license_plate", FieldType::String)
+            },
+            Field {
+                required: true,
+                min: Some(1),
+                ..Field::new("capacity", FieldType::Float)
+            },
+        ],
+        primary_key: None,
+        audited: false,
+        soft_delete: false,
+        versioned: false,
+    }
+}
+
+impl ProjectConfig {
+    /// Loads a configuration from a TOML, YAML or JSON file, picked by extension.
+    /// Fields missing from the file fall back to the wizard defaults.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        Ok(PartialProjectConfig::from_file(path)?.into_config())
+    }
+
+    /// Id type of the named entity, or of the built-in users table.
+    pub fn primary_key_of(&self, entity: &str) -> PrimaryKey {
+        self.entities
+            .iter()
+            .find(|e| e.name == entity)
+            .and_then(|e| e.primary_key)
+            .unwrap_or(self.primary_key)
+    }
+
+    /// The entities, enums and relationships of the project.
+    pub fn domain_model(&self) -> DomainModel {
+        DomainModel {
+            entities: self.entities.clone(),
+            enums: self.enums.clone(),
+            relationships: self.relationships.clone(),
+        }
+    }
+}
+
+/// A `ProjectConfig` in which every answer is optional. Config files, command-line
+/// flags and presets all produce one; the wizard only asks what is still missing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PartialProjectConfig {
+    pub name: Option<String>,
+    pub database: Option<Database>,
+    pub orm: Option<Orm>,
+    pub infrastructure: Option<Vec<Infrastructure>>,
+    pub frontend: Option<Frontend>,
+    pub authentication: Option<Authentication>,
+    pub devops: Option<DevOps>,
+    pub router_strategy: Option<RouterStrategy>,
+    pub api_ui: Option<ApiUi>,
+    pub hateoas: Option<bool>,
+    pub ai_agents: Option<Vec<AIAgent>>,
+    pub primary_key: Option<PrimaryKey>,
+    pub entities: Option<Vec<Entity>>,
+    pub enums: Option<Vec<EnumDef>>,
+    pub relationships: Option<Vec<Relationship>>,
+    pub contract: Option<ApiContract>,
+}
+
+impl PartialProjectConfig {
+    /// Reads a TOML, YAML or JSON file, picked by extension.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+
+        let config = match extension.as_deref() {
+            Some("toml") => toml::from_str(&content).map_err(anyhow::Error::from),
+            Some("yaml") | Some("yml") => serde_yaml::from_str(&content).map_err(anyhow::Error::from),
+            Some("json") => serde_json::from_str(&content).map_err(anyhow::Error::from),
+            _ => bail!(
+                "Unsupported config file '{}': expected a .toml, .yaml, .yml or .json extension",
+                path.display()
+            ),
+        };
+        config.with_context(|| format!("Invalid project config in {}", path.display()))
+    }
+
+    /// Writes the answers as TOML, YAML or JSON, picked by extension like `from_file`.
+    pub fn to_file(&self, path: &Path) -> Result<()> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+
+        let content = match extension.as_deref() {
+            Some("toml") => toml::to_string_pretty(self).map_err(anyhow::Error::from),
+            Some("yaml") | Some("yml") => serde_yaml::to_string(self).map_err(anyhow::Error::from),
+            Some("json") => serde_json::to_string_pretty(self).map_err(anyhow::Error::from),
+            _ => bail!(
+                "Unsupported config file '{}': expected a .toml, .yaml, .yml or .json extension",
+                path.display()
+            ),
+        }
+        .with_context(|| format!("Failed to serialize project config for {}", path.display()))?;
+        std::fs::write(path, content).with_context(|| format!("Failed to write config file {}", path.display()))
+    }
+
+    /// Layers `overrides` on top of `self`; answers present in `overrides` win.
+    pub fn merge(self, overrides: PartialProjectConfig) -> Self {
+        Self {
+            name: overrides.name.or(self.name),
+            database: overrides.database.or(self.database),
+            orm: overrides.orm.or(self.orm),
+            infrastructure: overrides.infrastructure.or(self.infrastructure),
+            frontend: overrides.frontend.or(self.frontend),
+            authentication: overrides.authentication.or(self.authentication),
+            devops: overrides.devops.or(self.devops),
+            router_strategy: overrides.router_strategy.or(self.router_strategy),
+            api_ui: overrides.api_ui.or(self.api_ui),
+            hateoas: overrides.hateoas.or(self.hateoas),
+            ai_agents: overrides.ai_agents.or(self.ai_agents),
+            primary_key: overrides.primary_key.or(self.primary_key),
+            entities: overrides.entities.or(self.entities),
+            enums: overrides.enums.or(self.enums),
+            relationships: overrides.relationships.or(self.relationships),
+            contract: overrides.contract.or(self.contract),
+        }
+    }
+
+    /// Overwrites the fields of `config` that this partial answers.
+    pub fn apply_to(self, mut config: ProjectConfig) -> ProjectConfig {
+        if let Some(name) = self.name {
+            config.name = name;
+        }
+        if let Some(database) = self.database {
+            config.database = database;
+        }
+        if let Some(orm) = self.orm {
+            config.orm = orm;
+        }
+        if let Some(infrastructure) = self.infrastructure {
+            config.infrastructure = infrastructure;
+        }
+        if let Some(frontend) = self.frontend {
+            config.frontend = frontend;
+        }
+        if let Some(authentication) = self.authentication {
+            config.authentication = authentication;
+        }
+        if let Some(devops) = self.devops {
+            config.devops = devops;
+        }
+        if let Some(router_strategy) = self.router_strategy {
+            config.router_strategy = router_strategy;
+        }
+        if let Some(api_ui) = self.api_ui {
+            config.api_ui = api_ui;
+        }
+        if let Some(hateoas) = self.hateoas {
+            config.hateoas = hateoas;
+        }
+        if let Some(ai_agents) = self.ai_agents {
+            config.ai_agents = ai_agents;
+        }
+        if let Some(primary_key) = self.primary_key {
+            config.primary_key = primary_key;
+        }
+        if let Some(entities) = self.entities {
+            config.entities = entities;
+        }
+        if let Some(enums) = self.enums {
+            config.enums = enums;
+        }
+        if let Some(relationships) = self.relationships {
+            config.relationships = relationships;
+        }
+        if let Some(contract) = self.contract {
+            config.contract = contract;
+        }
+        config
+    }
+
+    /// Fills every unanswered question with the default the wizard would pre-select.
+    pub fn into_config(self) -> ProjectConfig {
+        // The wizard never asks for an ORM on MongoDB; it uses the native driver,
+        // which has no sequences to assign ids.
+        let orm_answered = self.orm.is_some();
+        let primary_key_answered = self.primary_key.is_some();
+        let mut config = self.apply_to(ProjectConfig::default());
+        if !orm_answered && config.database == Database::MongoDB {
+            config.orm = Orm::None;
+        }
+        if !primary_key_answered && config.database == Database::MongoDB {
+            config.primary_key = PrimaryKey::UuidV7;
+        }
+        config
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -29,6 +273,20 @@ impl std::fmt::Display for Database {
     }
 }
 
+impl FromStr for Database {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "postgres" | "postgresql" => Ok(Database::Postgres),
+            "mysql" => Ok(Database::MySQL),
+            "mongodb" | "mongo" => Ok(Database::MongoDB),
+            "sqlite" => Ok(Database::SQLite),
+            _ => bail!("Unknown database '{}': expected one of postgres, mysql, mongodb, sqlite", s),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum Orm {
     Sqlx,
@@ -43,6 +301,20 @@ impl std::fmt::Display for Orm {
     }
 }
 
+impl FromStr for Orm {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "sqlx" => Ok(Orm::Sqlx),
+            "diesel" => Ok(Orm::Diesel),
+            "seaorm" | "sea-orm" => Ok(Orm::SeaOrm),
+            "none" => Ok(Orm::None),
+            _ => bail!("Unknown ORM '{}': expected one of sqlx, diesel, seaorm, none", s),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum Infrastructure {
     Redis,
@@ -56,6 +328,19 @@ impl std::fmt::Display for Infrastructure {
     }
 }
 
+impl FromStr for Infrastructure {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "redis" => Ok(Infrastructure::Redis),
+            "kafka" => Ok(Infrastructure::Kafka),
+            "socket" => Ok(Infrastructure::Socket),
+            _ => bail!("Unknown infrastructure '{}': expected one of redis, kafka, socket", s),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum Frontend {
     React,
@@ -71,6 +356,21 @@ impl std::fmt::Display for Frontend {
     }
 }
 
+impl FromStr for Frontend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "react" => Ok(Frontend::React),
+            "vue" => Ok(Frontend::Vue),
+            "svelte" => Ok(Frontend::Svelte),
+            "angular" => Ok(Frontend::Angular),
+            "none" => Ok(Frontend::None),
+            _ => bail!("Unknown frontend '{}': expected one of react, vue, svelte, angular, none", s),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Authentication {
     None,
@@ -79,6 +379,34 @@ pub enum Authentication {
     OAuth2(Vec<OAuthProvider>),
 }
 
+impl FromStr for Authentication {
+    type Err = anyhow::Error;
+
+    /// Parses the strategy name; `oauth2` yields an empty provider list to be filled in separately.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(Authentication::None),
+            "basic" => Ok(Authentication::Basic),
+            "jwt" => Ok(Authentication::Jwt),
+            "oauth2" => Ok(Authentication::OAuth2(Vec::new())),
+            _ => bail!("Unknown authentication '{}': expected one of none, basic, jwt, oauth2", s),
+        }
+    }
+}
+
+impl Authentication {
+    /// Combines a strategy and a provider list given separately, as on the
+    /// command line: providers alone imply OAuth2, and providers with any other
+    /// strategy are an error rather than dropped.
+    pub fn with_providers(auth: Option<Authentication>, providers: Option<Vec<OAuthProvider>>) -> Result<Option<Authentication>> {
+        match (auth, providers) {
+            (None | Some(Authentication::OAuth2(_)), Some(providers)) => Ok(Some(Authentication::OAuth2(providers))),
+            (Some(auth), Some(_)) => bail!("OAuth2 providers only apply to oauth2 authentication, not {:?}", auth),
+            (auth, None) => Ok(auth),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum OAuthProvider {
     Discord,
@@ -93,11 +421,32 @@ impl std::fmt::Display for OAuthProvider {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl FromStr for OAuthProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "discord" => Ok(OAuthProvider::Discord),
+            "google" => Ok(OAuthProvider::Google),
+            "apple" => Ok(OAuthProvider::Apple),
+            "github" => Ok(OAuthProvider::GitHub),
+            _ => bail!("Unknown OAuth provider '{}': expected one of discord, google, apple, github", s),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct DevOps {
     pub docker_compose: bool,
 }
 
+impl Default for DevOps {
+    fn default() -> Self {
+        Self { docker_compose: true }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum RouterStrategy {
     Standard,
@@ -111,6 +460,19 @@ impl std::fmt::Display for RouterStrategy {
     }
 }
 
+impl FromStr for RouterStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "standard" => Ok(RouterStrategy::Standard),
+            "axum-controller" | "axumcontroller" => Ok(RouterStrategy::AxumController),
+            "axum-folder-router" | "axumfolderrouter" => Ok(RouterStrategy::AxumFolderRouter),
+            _ => bail!("Unknown router strategy '{}': expected one of standard, axum-controller, axum-folder-router", s),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum ApiUi {
     Swagger,
@@ -124,6 +486,19 @@ impl std::fmt::Display for ApiUi {
     }
 }
 
+impl FromStr for ApiUi {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "swagger" => Ok(ApiUi::Swagger),
+            "scalar" => Ok(ApiUi::Scalar),
+            "none" => Ok(ApiUi::None),
+            _ => bail!("Unknown API UI '{}': expected one of swagger, scalar, none", s),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum AIAgent {
     Claude,
@@ -136,3 +511,16 @@ impl std::fmt::Display for AIAgent {
         write!(f, "{:?}", self)
     }
 }
+
+impl FromStr for AIAgent {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "claude" => Ok(AIAgent::Claude),
+            "gemini" => Ok(AIAgent::Gemini),
+            "gpt" => Ok(AIAgent::GPT),
+            _ => bail!("Unknown AI agent '{}': expected one of claude, gemini, gpt", s),
+        }
+    }
+}
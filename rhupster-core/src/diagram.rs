@@ -0,0 +1,214 @@
+//! Entity relationship diagrams of a domain model, as Mermaid `erDiagram`
+//! or PlantUML:
+//!
+//! ```text
+//! erDiagram
+//!     Truck {
+//!         u64 id PK
+//!         string license_plate UK "required"
+//!         u64 driver_id FK "required"
+//!     }
+//!     Truck }o--|| User : driver
+//! ```
+//!
+//! Every entity lists its id, its fields and the foreign keys it holds;
+//! entities that are only referenced, like the built-in `User`, just their id.
+//! Relationships point from their owner to their target, named after the
+//! owner's relation field.
+
+use crate::generator::naming::snake_case;
+use crate::model::{DomainModel, Entity, FieldType, PrimaryKey, Relationship, RelationshipKind};
+use anyhow::{bail, Result};
+use std::fmt::Write;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiagramFormat {
+    #[default]
+    Mermaid,
+    PlantUml,
+}
+
+impl std::fmt::Display for DiagramFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiagramFormat::Mermaid => write!(f, "mermaid"),
+            DiagramFormat::PlantUml => write!(f, "plantuml"),
+        }
+    }
+}
+
+impl FromStr for DiagramFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "mermaid" | "mmd" => Ok(DiagramFormat::Mermaid),
+            "plantuml" | "puml" => Ok(DiagramFormat::PlantUml),
+            _ => bail!("Unknown diagram format '{}': expected mermaid or plantuml", s),
+        }
+    }
+}
+
+/// Renders `model` in `format`. Entities without their own `primary_key` get
+/// `primary_key`.
+pub fn render(model: &DomainModel, primary_key: PrimaryKey, format: DiagramFormat) -> String {
+    match format {
+        DiagramFormat::Mermaid => mermaid(model, primary_key),
+        DiagramFormat::PlantUml => plantuml(model, primary_key),
+    }
+}
+
+/// One row of an entity box.
+struct Column {
+    ty: String,
+    name: String,
+    /// `PK`, `FK` or `UK`.
+    key: Option<&'static str>,
+    required: bool,
+}
+
+fn columns(entity: &Entity, model: &DomainModel, primary_key: PrimaryKey) -> Vec<Column> {
+    let mut columns = vec![Column {
+        ty: id_type(entity.primary_key.unwrap_or(primary_key)),
+        name: "id".to_string(),
+        key: Some("PK"),
+        required: true,
+    }];
+    for field in &entity.fields {
+        columns.push(Column {
+            ty: field_type(&field.field_type),
+            name: field.name.clone(),
+            key: field.unique.then_some("UK"),
+            required: field.required,
+        });
+    }
+    for relationship in model.relationships.iter().filter(|r| r.owner() == entity.name && holds_key(r)) {
+        let target_key = model
+            .entities
+            .iter()
+            .find(|e| e.name == relationship.target())
+            .and_then(|e| e.primary_key)
+            .unwrap_or(primary_key);
+        columns.push(Column {
+            ty: id_type(target_key),
+            name: format!("{}_id", relation_name(relationship)),
+            key: Some("FK"),
+            required: relationship.required,
+        });
+    }
+    columns
+}
+
+/// The declared entities followed by those only named in relationships.
+fn entities(model: &DomainModel) -> Vec<Entity> {
+    let mut entities = model.entities.clone();
+    for relationship in &model.relationships {
+        for name in [relationship.owner(), relationship.target()] {
+            if !entities.iter().any(|e| e.name == name) {
+                entities.push(Entity::new(name));
+            }
+        }
+    }
+    entities
+}
+
+/// Whether the owner stores the relationship as a foreign key column rather than a join table.
+fn holds_key(relationship: &Relationship) -> bool {
+    relationship.kind != RelationshipKind::ManyToMany
+}
+
+/// Name of the relation field on the owner; defaults to the target's snake_case name.
+fn relation_name(relationship: &Relationship) -> String {
+    relationship
+        .owner_field()
+        .map(String::from)
+        .unwrap_or_else(|| snake_case(relationship.target()))
+}
+
+fn id_type(primary_key: PrimaryKey) -> String {
+    match primary_key {
+        PrimaryKey::U64 => "u64",
+        PrimaryKey::I64 => "i64",
+        PrimaryKey::UuidV4 | PrimaryKey::UuidV7 => "uuid",
+        PrimaryKey::Ulid => "ulid",
+    }
+    .to_string()
+}
+
+fn field_type(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::Enum(name) => name.clone(),
+        other => other.to_string().to_ascii_lowercase(),
+    }
+}
+
+/// Crow's foot notation, read from the owner's side: `Truck }o--|| User`.
+/// Mermaid and PlantUML share it.
+fn cardinality(relationship: &Relationship) -> &'static str {
+    match (relationship.kind, relationship.required) {
+        // The owner of a one-to-many is its "many" side, holding the key.
+        (RelationshipKind::ManyToOne | RelationshipKind::OneToMany, true) => "}o--||",
+        (RelationshipKind::ManyToOne | RelationshipKind::OneToMany, false) => "}o--o|",
+        (RelationshipKind::OneToOne, true) => "|o--||",
+        (RelationshipKind::OneToOne, false) => "|o--o|",
+        (RelationshipKind::ManyToMany, _) => "}o--o{",
+    }
+}
+
+pub fn mermaid(model: &DomainModel, primary_key: PrimaryKey) -> String {
+    let mut out = String::from("erDiagram\n");
+    for entity in &entities(model) {
+        let _ = writeln!(out, "    {} {{", entity.name);
+        for column in columns(entity, model, primary_key) {
+            let _ = write!(out, "        {} {}", column.ty, column.name);
+            if let Some(key) = column.key {
+                let _ = write!(out, " {}", key);
+            }
+            if column.required && column.key != Some("PK") {
+                out.push_str(" \"required\"");
+            }
+            out.push('\n');
+        }
+        out.push_str("    }\n");
+    }
+    for relationship in &model.relationships {
+        let _ = writeln!(
+            out,
+            "    {} {} {} : {}",
+            relationship.owner(),
+            cardinality(relationship),
+            relationship.target(),
+            relation_name(relationship)
+        );
+    }
+    out
+}
+
+pub fn plantuml(model: &DomainModel, primary_key: PrimaryKey) -> String {
+    let mut out = String::from("@startuml\nhide circle\nskinparam linetype ortho\n\n");
+    for entity in &entities(model) {
+        let _ = writeln!(out, "entity {} {{", entity.name);
+        for (i, column) in columns(entity, model, primary_key).into_iter().enumerate() {
+            let marker = if column.required { "*" } else { "" };
+            let key = column.key.map(|k| format!(" <<{}>>", k)).unwrap_or_default();
+            let _ = writeln!(out, "  {}{} : {}{}", marker, column.name, column.ty, key);
+            if i == 0 {
+                out.push_str("  --\n");
+            }
+        }
+        out.push_str("}\n\n");
+    }
+    for relationship in &model.relationships {
+        let _ = writeln!(
+            out,
+            "{} {} {} : {}",
+            relationship.owner(),
+            cardinality(relationship),
+            relationship.target(),
+            relation_name(relationship)
+        );
+    }
+    out.push_str("@enduml\n");
+    out
+}
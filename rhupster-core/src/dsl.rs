@@ -0,0 +1,584 @@
+//! Parser for `.rhdl` domain files, a JDL-like description of entities,
+//! enums and relationships:
+//!
+//! ```text
+//! enum TruckStatus { AVAILABLE, IN_TRANSIT, MAINTENANCE }
+//!
+//! entity Truck {
+//!     license_plate String required unique pattern(/^[A-Z0-9-]+$/)
+//!     capacity Float min(0) max(40000)
+//!     status TruckStatus required
+//! }
+//!
+//! relationship ManyToOne {
+//!     Truck{driver required eager} to User
+//! }
+//! ```
+//!
+//! Constraints follow the field type on the same line. `min`/`max` bound the
+//! length of strings and the value of numbers. In a relationship, `required`
+//! and `eager` after the field name make the reference mandatory and load the
+//! related entity along with the owner.
+
+use crate::model::{DomainModel, Entity, EnumDef, FetchType, Field, FieldType, Relationship, RelationshipKind};
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Entities every generated project already has, which relationships may point at.
+const BUILTIN_ENTITIES: [&str; 1] = ["User"];
+
+/// A 1-based position in the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DslError {
+    pub span: Span,
+    pub message: String,
+}
+
+impl DslError {
+    pub(crate) fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for DslError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.span.line, self.span.column, self.message)
+    }
+}
+
+impl std::error::Error for DslError {}
+
+/// Parses the contents of a `.rhdl` file.
+pub fn parse(source: &str) -> Result<DomainModel, DslError> {
+    let tokens = Lexer::new(source).tokenize()?;
+    let parsed = Parser { tokens, pos: 0 }.parse_file()?;
+    parsed.resolve()
+}
+
+/// Reads and parses a `.rhdl` file; errors are reported as `path:line:column`.
+pub fn parse_file(path: &Path) -> Result<DomainModel> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read domain file {}", path.display()))?;
+    parse(&source).map_err(|e| anyhow::anyhow!("{}:{}: {}", path.display(), e.span, e.message))
+}
+
+/// Tokens shared with the JDL importer. `Symbol` covers punctuation that only
+/// JDL gives a meaning to, such as `@` or `*`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    Ident(String),
+    Int(i64),
+    Str(String),
+    Regex(String),
+    LBrace,
+    RBrace,
+    LParen,
+    RParen,
+    Comma,
+    Symbol(char),
+    Eof,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "'{}'", name),
+            Token::Int(n) => write!(f, "'{}'", n),
+            Token::Str(s) => write!(f, "\"{}\"", s),
+            Token::Regex(r) => write!(f, "/{}/", r),
+            Token::LBrace => write!(f, "'{{'"),
+            Token::RBrace => write!(f, "'}}'"),
+            Token::LParen => write!(f, "'('"),
+            Token::RParen => write!(f, "')'"),
+            Token::Comma => write!(f, "','"),
+            Token::Symbol(c) => write!(f, "'{}'", c),
+            Token::Eof => write!(f, "end of file"),
+        }
+    }
+}
+
+pub(crate) struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub(crate) fn new(source: &'a str) -> Self {
+        Self {
+            chars: source.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn span(&self) -> Span {
+        Span {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    pub(crate) fn tokenize(mut self) -> Result<Vec<(Token, Span)>, DslError> {
+        let mut tokens = Vec::new();
+        loop {
+            let span = self.span();
+            let Some(c) = self.bump() else {
+                tokens.push((Token::Eof, span));
+                return Ok(tokens);
+            };
+            let token = match c {
+                c if c.is_whitespace() => continue,
+                '{' => Token::LBrace,
+                '}' => Token::RBrace,
+                '(' => Token::LParen,
+                ')' => Token::RParen,
+                ',' => Token::Comma,
+                '@' | '*' | '[' | ']' | '=' | '.' | ':' | ';' => Token::Symbol(c),
+                '/' => match self.chars.peek() {
+                    Some('/') => {
+                        while self.chars.peek().is_some_and(|&c| c != '\n') {
+                            self.bump();
+                        }
+                        continue;
+                    }
+                    Some('*') => {
+                        self.bump();
+                        self.block_comment(span)?;
+                        continue;
+                    }
+                    _ => Token::Regex(self.delimited('/', span, "regular expression")?),
+                },
+                '"' => Token::Str(self.delimited('"', span, "string")?),
+                c if c.is_ascii_digit() || c == '-' => self.number(c, span)?,
+                c if c.is_alphabetic() || c == '_' => {
+                    let mut ident = c.to_string();
+                    while let Some(&c) = self.chars.peek() {
+                        // JDL option values such as `infinite-scroll` contain dashes.
+                        let dashed = c == '-' && self.chars.clone().nth(1).is_some_and(|c| c.is_alphabetic());
+                        if !(c.is_alphanumeric() || c == '_' || dashed) {
+                            break;
+                        }
+                        ident.push(c);
+                        self.bump();
+                    }
+                    Token::Ident(ident)
+                }
+                c => return Err(DslError::new(span, format!("Unexpected character '{}'", c))),
+            };
+            tokens.push((token, span));
+        }
+    }
+
+    fn block_comment(&mut self, start: Span) -> Result<(), DslError> {
+        while let Some(c) = self.bump() {
+            if c == '*' && self.chars.peek() == Some(&'/') {
+                self.bump();
+                return Ok(());
+            }
+        }
+        Err(DslError::new(start, "Unterminated comment"))
+    }
+
+    /// Reads up to the closing `delimiter`; a backslash keeps the next character
+    /// (and, inside regular expressions, the backslash itself).
+    fn delimited(&mut self, delimiter: char, start: Span, what: &str) -> Result<String, DslError> {
+        let mut value = String::new();
+        while let Some(c) = self.bump() {
+            match c {
+                c if c == delimiter => return Ok(value),
+                '\n' => break,
+                '\\' => {
+                    let Some(escaped) = self.bump() else { break };
+                    if delimiter == '/' && escaped != '/' {
+                        value.push('\\');
+                    }
+                    value.push(escaped);
+                }
+                c => value.push(c),
+            }
+        }
+        Err(DslError::new(start, format!("Unterminated {}", what)))
+    }
+
+    fn number(&mut self, first: char, start: Span) -> Result<Token, DslError> {
+        let mut digits = first.to_string();
+        while let Some(&c) = self.chars.peek() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+            digits.push(c);
+            self.bump();
+        }
+        digits
+            .parse()
+            .map(Token::Int)
+            .map_err(|_| DslError::new(start, format!("Invalid number '{}'", digits)))
+    }
+}
+
+/// A field whose type is still a name, resolved once every enum is known.
+struct RawField {
+    field: Field,
+    type_name: String,
+    type_span: Span,
+    constraint_spans: Vec<(&'static str, Span)>,
+}
+
+struct RawEntity {
+    name: String,
+    span: Span,
+    fields: Vec<RawField>,
+}
+
+struct RawRelationship {
+    relationship: Relationship,
+    from_span: Span,
+    to_span: Span,
+}
+
+#[derive(Default)]
+struct ParsedFile {
+    entities: Vec<RawEntity>,
+    enums: Vec<(EnumDef, Span)>,
+    relationships: Vec<RawRelationship>,
+}
+
+struct Parser {
+    tokens: Vec<(Token, Span)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &(Token, Span) {
+        &self.tokens[self.pos]
+    }
+
+    fn next(&mut self) -> (Token, Span) {
+        let token = self.tokens[self.pos].clone();
+        if token.0 != Token::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek().0 == *token {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: Token) -> Result<Span, DslError> {
+        let (found, span) = self.next();
+        if found == token {
+            Ok(span)
+        } else {
+            Err(DslError::new(span, format!("Expected {}, found {}", token, found)))
+        }
+    }
+
+    fn ident(&mut self, what: &str) -> Result<(String, Span), DslError> {
+        match self.next() {
+            (Token::Ident(name), span) => Ok((name, span)),
+            (found, span) => Err(DslError::new(span, format!("Expected {}, found {}", what, found))),
+        }
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(&self.peek().0, Token::Ident(name) if name == keyword)
+    }
+
+    fn parse_file(mut self) -> Result<ParsedFile, DslError> {
+        let mut file = ParsedFile::default();
+        loop {
+            match self.next() {
+                (Token::Eof, _) => return Ok(file),
+                (Token::Ident(keyword), _) if keyword == "entity" => file.entities.push(self.entity()?),
+                (Token::Ident(keyword), _) if keyword == "enum" => file.enums.push(self.enum_def()?),
+                (Token::Ident(keyword), _) if keyword == "relationship" => {
+                    file.relationships.extend(self.relationships()?)
+                }
+                (found, span) => {
+                    return Err(DslError::new(
+                        span,
+                        format!("Expected 'entity', 'enum' or 'relationship', found {}", found),
+                    ))
+                }
+            }
+        }
+    }
+
+    fn entity(&mut self) -> Result<RawEntity, DslError> {
+        let (name, span) = self.ident("an entity name")?;
+        let mut fields = Vec::new();
+        if self.eat(&Token::LBrace) {
+            while !self.eat(&Token::RBrace) {
+                fields.push(self.field()?);
+                self.eat(&Token::Comma);
+            }
+        }
+        Ok(RawEntity { name, span, fields })
+    }
+
+    fn field(&mut self) -> Result<RawField, DslError> {
+        let (name, name_span) = self.ident("a field name or '}'")?;
+        let (type_name, type_span) = self.ident(&format!("a type for field '{}'", name))?;
+        let mut raw = RawField {
+            field: Field::new(name, FieldType::String),
+            type_name,
+            type_span,
+            constraint_spans: Vec::new(),
+        };
+
+        // Constraints stay on the field's line; an identifier on the next line starts a new field.
+        while let (Token::Ident(keyword), span) = self.peek().clone() {
+            if span.line != name_span.line {
+                break;
+            }
+            self.pos += 1;
+            match keyword.as_str() {
+                "required" => raw.field.required = true,
+                "unique" => raw.field.unique = true,
+                "min" => {
+                    raw.field.min = Some(self.int_argument("min")?);
+                    raw.constraint_spans.push(("min", span));
+                }
+                "max" => {
+                    raw.field.max = Some(self.int_argument("max")?);
+                    raw.constraint_spans.push(("max", span));
+                }
+                "pattern" => {
+                    self.expect(Token::LParen)?;
+                    match self.next() {
+                        (Token::Str(pattern), _) | (Token::Regex(pattern), _) => raw.field.pattern = Some(pattern),
+                        (found, span) => {
+                            return Err(DslError::new(
+                                span,
+                                format!("Expected a pattern like /^[A-Z]+$/, found {}", found),
+                            ))
+                        }
+                    }
+                    self.expect(Token::RParen)?;
+                    raw.constraint_spans.push(("pattern", span));
+                }
+                other => {
+                    return Err(DslError::new(
+                        span,
+                        format!(
+                            "Unknown constraint '{}': expected required, unique, min, max or pattern",
+                            other
+                        ),
+                    ))
+                }
+            }
+        }
+        Ok(raw)
+    }
+
+    fn int_argument(&mut self, constraint: &str) -> Result<i64, DslError> {
+        self.expect(Token::LParen)?;
+        let value = match self.next() {
+            (Token::Int(value), _) => value,
+            (found, span) => {
+                return Err(DslError::new(
+                    span,
+                    format!("Expected a whole number for {}, found {}", constraint, found),
+                ))
+            }
+        };
+        self.expect(Token::RParen)?;
+        Ok(value)
+    }
+
+    fn enum_def(&mut self) -> Result<(EnumDef, Span), DslError> {
+        let (name, span) = self.ident("an enum name")?;
+        self.expect(Token::LBrace)?;
+        let mut values = Vec::new();
+        while !self.eat(&Token::RBrace) {
+            let (value, value_span) = self.ident("an enum value or '}'")?;
+            if values.contains(&value) {
+                return Err(DslError::new(
+                    value_span,
+                    format!("Value '{}' appears twice in enum '{}'", value, name),
+                ));
+            }
+            values.push(value);
+            self.eat(&Token::Comma);
+        }
+        if values.is_empty() {
+            return Err(DslError::new(span, format!("Enum '{}' has no values", name)));
+        }
+        Ok((EnumDef::new(name, values), span))
+    }
+
+    fn relationships(&mut self) -> Result<Vec<RawRelationship>, DslError> {
+        let (kind, kind_span) = self.ident("a relationship kind")?;
+        let kind: RelationshipKind = kind
+            .parse()
+            .map_err(|e: anyhow::Error| DslError::new(kind_span, e.to_string()))?;
+        self.expect(Token::LBrace)?;
+
+        let mut relationships = Vec::new();
+        while !self.eat(&Token::RBrace) {
+            let (from, from_span) = self.ident("an entity name or '}'")?;
+            let (from_field, required, fetch) = self.relationship_field()?;
+            match self.next() {
+                (Token::Ident(keyword), _) if keyword == "to" => {}
+                (found, span) => return Err(DslError::new(span, format!("Expected 'to', found {}", found))),
+            }
+            let (to, to_span) = self.ident("an entity name")?;
+            let (to_field, _, _) = self.relationship_field()?;
+            relationships.push(RawRelationship {
+                relationship: Relationship {
+                    kind,
+                    from,
+                    from_field,
+                    to,
+                    to_field,
+                    required,
+                    fetch,
+                },
+                from_span,
+                to_span,
+            });
+            self.eat(&Token::Comma);
+        }
+        Ok(relationships)
+    }
+
+    /// The optional `{field}`, `{field required}` or `{field required eager}` after an
+    /// entity in a relationship.
+    fn relationship_field(&mut self) -> Result<(Option<String>, bool, FetchType), DslError> {
+        if !self.eat(&Token::LBrace) {
+            return Ok((None, false, FetchType::Lazy));
+        }
+        let (field, _) = self.ident("a relationship field name")?;
+        let (mut required, mut fetch) = (false, FetchType::Lazy);
+        loop {
+            if self.peek_keyword("required") {
+                required = true;
+            } else if self.peek_keyword("eager") {
+                fetch = FetchType::Eager;
+            } else {
+                break;
+            }
+            self.pos += 1;
+        }
+        self.expect(Token::RBrace)?;
+        Ok((Some(field), required, fetch))
+    }
+}
+
+impl ParsedFile {
+    /// Resolves field types against the declared enums and checks that names are unique
+    /// and relationships point at known entities.
+    fn resolve(self) -> Result<DomainModel, DslError> {
+        let mut enum_names = HashSet::new();
+        for (def, span) in &self.enums {
+            if !enum_names.insert(def.name.as_str()) {
+                return Err(DslError::new(*span, format!("Enum '{}' is declared more than once", def.name)));
+            }
+        }
+
+        let mut entity_names: HashSet<&str> = BUILTIN_ENTITIES.into_iter().collect();
+        let mut entities = Vec::new();
+        for raw in &self.entities {
+            if enum_names.contains(raw.name.as_str()) || !entity_names.insert(raw.name.as_str()) {
+                return Err(DslError::new(raw.span, format!("'{}' is declared more than once", raw.name)));
+            }
+            let mut entity = Entity::new(raw.name.clone());
+            for raw_field in &raw.fields {
+                entity.fields.push(resolve_field(raw_field, &enum_names)?);
+            }
+            entities.push(entity);
+        }
+
+        let mut relationships = Vec::new();
+        for raw in self.relationships {
+            for (name, span) in [(&raw.relationship.from, raw.from_span), (&raw.relationship.to, raw.to_span)] {
+                if !entity_names.contains(name.as_str()) {
+                    return Err(DslError::new(span, format!("Relationship refers to unknown entity '{}'", name)));
+                }
+            }
+            relationships.push(raw.relationship);
+        }
+
+        Ok(DomainModel {
+            entities,
+            enums: self.enums.into_iter().map(|(def, _)| def).collect(),
+            relationships,
+        })
+    }
+}
+
+fn resolve_field(raw: &RawField, enum_names: &HashSet<&str>) -> Result<Field, DslError> {
+    let field_type = match raw.type_name.parse::<FieldType>() {
+        Ok(field_type) => field_type,
+        Err(_) if enum_names.contains(raw.type_name.as_str()) => FieldType::Enum(raw.type_name.clone()),
+        Err(_) => {
+            return Err(DslError::new(
+                raw.type_span,
+                format!(
+                    "Unknown type '{}' for field '{}': expected string, int, long, float, double, decimal, bool, uuid, datetime or a declared enum",
+                    raw.type_name, raw.field.name
+                ),
+            ))
+        }
+    };
+
+    for (constraint, span) in &raw.constraint_spans {
+        let allowed = match *constraint {
+            "pattern" => field_type == FieldType::String,
+            _ => field_type == FieldType::String || field_type.is_numeric(),
+        };
+        if !allowed {
+            return Err(DslError::new(
+                *span,
+                format!("'{}' cannot be used on field '{}' of type {}", constraint, raw.field.name, field_type),
+            ));
+        }
+    }
+    if let (Some(min), Some(max)) = (raw.field.min, raw.field.max) {
+        if min > max {
+            return Err(DslError::new(
+                raw.type_span,
+                format!("Field '{}' has min({}) greater than max({})", raw.field.name, min, max),
+            ));
+        }
+    }
+
+    Ok(Field {
+        field_type,
+        ..raw.field.clone()
+    })
+}
@@ -1,11 +1,13 @@
-use crate::config::ProjectConfig;
-use anyhow::{Result, anyhow};
+use anyhow::{Result, bail};
 use std::path::Path;
-use super::utils::{render_dir_recursive, TemplateSource};
+use tera::Context as TeraContext;
+use super::utils::{OutputTree, TemplateSet};
 
-pub async fn generate(config: &ProjectConfig, template_root: TemplateSource<'_>, output_dir: &Path) -> Result<()> {
+pub async fn generate(templates: &TemplateSet, context: &TeraContext, output_dir: &Path, out: &mut OutputTree) -> Result<()> {
     // Copy application crate templates
-    let src_template_dir = template_root.join("application").ok_or_else(|| anyhow!("Template directory 'application' not found"))?;
-    render_dir_recursive(config, src_template_dir, output_dir).await?;
+    if !templates.contains("application") {
+        bail!("Template directory 'application' not found");
+    }
+    templates.render_dir("application", output_dir, context, None, out)?;
     Ok(())
 }
@@ -0,0 +1,85 @@
+//! Blueprints keep project or company specific changes outside the main
+//! templates. A blueprint is a template directory with a `blueprint.toml`:
+//!
+//! ```toml
+//! name = "acme"
+//! description = "Acme logging conventions and middleware"
+//!
+//! [[steps]]
+//! name = "observability"
+//! templates = "steps/observability"
+//! output = "observability"
+//! ```
+//!
+//! Its other files are layered over the base templates by relative path, so
+//! `api/src/error.rs.tera` replaces the base error mapping and a path the base
+//! does not have adds a file. Each step renders one directory of the blueprint
+//! into the project, below `output`, after the built-in steps.
+
+use super::utils::{OutputTree, TemplateSet, TemplateSource};
+use tera::Context as TeraContext;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::path::{Component, Path};
+
+/// Manifest at the root of every blueprint.
+pub const BLUEPRINT_FILE_NAME: &str = "blueprint.toml";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlueprintManifest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub steps: Vec<BlueprintStep>,
+}
+
+/// Renders the `templates` directory of the blueprint into `output`, both
+/// relative paths; `output` defaults to the project root.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlueprintStep {
+    pub name: String,
+    pub templates: String,
+    #[serde(default)]
+    pub output: String,
+}
+
+#[derive(Clone)]
+pub struct Blueprint<'a> {
+    pub manifest: BlueprintManifest,
+    pub templates: TemplateSource<'a>,
+}
+
+impl<'a> Blueprint<'a> {
+    /// Reads the manifest of the blueprint at `templates`.
+    pub fn load(templates: TemplateSource<'a>) -> Result<Self> {
+        let origin = templates.describe();
+        if !templates.has_file(BLUEPRINT_FILE_NAME) {
+            bail!("{} is not a blueprint: it has no {}", origin, BLUEPRINT_FILE_NAME);
+        }
+        let contents = templates.tree().file(Path::new(BLUEPRINT_FILE_NAME))?;
+        let manifest: BlueprintManifest = toml::from_str(&String::from_utf8_lossy(&contents))
+            .with_context(|| format!("Invalid {} in {}", BLUEPRINT_FILE_NAME, origin))?;
+        for step in &manifest.steps {
+            for (key, path) in [("templates", &step.templates), ("output", &step.output)] {
+                if !Path::new(path).components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir)) {
+                    bail!("Step '{}' of blueprint '{}' has {} = \"{}\", which leaves its directory", step.name, manifest.name, key, path);
+                }
+            }
+        }
+        Ok(Self { manifest, templates })
+    }
+
+    /// Renders the blueprint's steps into `output_dir`.
+    pub fn generate(&self, context: &TeraContext, output_dir: &Path, out: &mut OutputTree) -> Result<()> {
+        for step in &self.manifest.steps {
+            let Some(step_templates) = self.templates.join(&step.templates).filter(|t| t.exists()) else {
+                bail!("Step '{}' of blueprint '{}' has no templates directory '{}'", step.name, self.manifest.name, step.templates);
+            };
+            TemplateSet::load(step_templates.tree())?.render_all(&output_dir.join(&step.output), context, None, out)?;
+        }
+        Ok(())
+    }
+}
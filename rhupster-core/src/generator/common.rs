@@ -1,15 +1,11 @@
-use crate::config::ProjectConfig;
 use anyhow::Result;
 use std::path::Path;
-use super::utils::{render_dir_recursive, TemplateSource};
+use tera::Context as TeraContext;
+use super::utils::{OutputTree, TemplateSet};
 
-pub async fn generate(config: &ProjectConfig, template_root: TemplateSource<'_>, output_dir: &Path) -> Result<()> {
-    let common_dir = template_root.join("common");
-    
-    let exists = common_dir.as_ref().map(|t| t.exists()).unwrap_or(false);
-
-    if exists {
-         render_dir_recursive(config, common_dir.unwrap(), output_dir).await?;
+pub async fn generate(templates: &TemplateSet, context: &TeraContext, output_dir: &Path, out: &mut OutputTree) -> Result<()> {
+    if templates.contains("common") {
+         templates.render_dir("common", output_dir, context, None, out)?;
     }
     Ok(())
 }
@@ -0,0 +1,64 @@
+use anyhow::Result;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// What to do with a file that already exists on disk with different contents.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resolution {
+    Overwrite,
+    Skip,
+    /// Stop before anything is written.
+    Abort,
+}
+
+/// Decides, file by file, how to handle existing files that would be overwritten.
+pub trait ConflictResolver {
+    fn resolve(&mut self, path: &Path, existing: &[u8], generated: &[u8]) -> Result<Resolution>;
+}
+
+/// Non-interactive conflict handling.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ConflictPolicy {
+    /// Refuse to touch a modified file (the default for library callers).
+    #[default]
+    Fail,
+    /// Replace every modified file.
+    Overwrite,
+    /// Keep every modified file as it is.
+    Skip,
+}
+
+impl ConflictResolver for ConflictPolicy {
+    fn resolve(&mut self, path: &Path, _existing: &[u8], _generated: &[u8]) -> Result<Resolution> {
+        match self {
+            ConflictPolicy::Overwrite => Ok(Resolution::Overwrite),
+            ConflictPolicy::Skip => Ok(Resolution::Skip),
+            ConflictPolicy::Fail => Err(ConflictError { path: path.to_path_buf(), policy: *self }.into()),
+        }
+    }
+}
+
+/// A modified file that `policy` refused to replace; nothing was written.
+/// Front ends word how to resolve it, such as which flag to pass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictError {
+    pub path: PathBuf,
+    pub policy: ConflictPolicy,
+}
+
+impl fmt::Display for ConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} already exists and differs from the generated version; nothing was written", self.path.display())
+    }
+}
+
+impl std::error::Error for ConflictError {}
+
+/// What happened to each generated file once written.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WriteSummary {
+    pub created: Vec<PathBuf>,
+    pub overwritten: Vec<PathBuf>,
+    pub skipped: Vec<PathBuf>,
+    pub unchanged: Vec<PathBuf>,
+}
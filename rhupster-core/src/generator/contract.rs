@@ -0,0 +1,383 @@
+use crate::config::ProjectConfig;
+use crate::model::{ApiContract, FieldType, HttpMethod, Operation, ParameterLocation};
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+use std::str::FromStr;
+use super::entities::{rust_type_of, strategy_path};
+use super::naming::{rust_ident, snake_case};
+use super::utils::{OutputTree, TemplateSet};
+use tera::Context as TeraContext;
+
+/// The imported API contract, exposed to templates as `contract`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ContractContext {
+    pub schemas: Vec<SchemaContext>,
+    /// Operations grouped by tag, one handler module per group.
+    pub groups: Vec<OperationGroupContext>,
+    /// Lowercase methods used by any operation, e.g. `["get", "patch"]`.
+    pub methods: Vec<String>,
+}
+
+/// A component schema generated as a DTO in `dto/contract.rs`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaContext {
+    pub name: String,
+    pub properties: Vec<PropertyContext>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PropertyContext {
+    /// Name in the contract, e.g. `licensePlate`.
+    pub name: String,
+    /// Rust field name, e.g. `license_plate`.
+    pub field: String,
+    /// Whether the field needs `#[serde(rename)]` to keep the contract's name.
+    pub renamed: bool,
+    pub rust_type: String,
+}
+
+/// The operations sharing a tag, e.g. `Fleet` generated as `fleet_operations`.
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationGroupContext {
+    pub name: String,
+    pub module: String,
+    pub operations: Vec<OperationContext>,
+    /// One route per path, with the handlers of each method on it.
+    pub routes: Vec<RouteContext>,
+    pub methods: Vec<String>,
+    pub uses_path: bool,
+    pub uses_query: bool,
+    pub uses_json: bool,
+    pub uses_status_code: bool,
+    pub uses_contract: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteContext {
+    /// Axum path, e.g. `/trucks/:truckId`.
+    pub path: String,
+    pub handlers: Vec<RouteHandler>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteHandler {
+    pub method: String,
+    pub name: String,
+}
+
+/// A handler stub documented with the contract's `#[utoipa::path]`.
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationContext {
+    pub name: String,
+    pub operation_id: Option<String>,
+    pub method: String,
+    /// OpenAPI path, e.g. `/trucks/{truckId}`.
+    pub path: String,
+    pub tag: Option<String>,
+    pub summary: Option<String>,
+    pub params: Vec<ParamContext>,
+    /// `_truck_id` or `(_truck_id, _stop_id)`, if the path has parameters.
+    pub path_binding: Option<String>,
+    /// `u64` or `(u64, String)`, matching `path_binding`.
+    pub path_type: Option<String>,
+    pub query: bool,
+    pub request_body: Option<String>,
+    pub responses: Vec<ResponseContext>,
+    /// The success type of the stub, e.g. `Json<contract::Truck>` or `StatusCode`.
+    pub return_type: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ParamContext {
+    pub name: String,
+    pub rust_type: String,
+    /// `Path`, `Query` or `Header`, as `utoipa::path` spells it.
+    pub location: String,
+    /// Escaped for a string literal; the parameter name when the contract has none.
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResponseContext {
+    pub status: u16,
+    /// Escaped for a string literal.
+    pub description: String,
+    pub body: Option<String>,
+}
+
+impl ContractContext {
+    pub fn new(config: &ProjectConfig) -> Self {
+        let contract = &config.contract;
+        let schemas = contract
+            .schemas
+            .iter()
+            .map(|schema| SchemaContext {
+                name: schema.name.clone(),
+                properties: schema
+                    .properties
+                    .iter()
+                    .map(|property| {
+                        let field = rust_ident(&property.name);
+                        let mut rust_type = contract_rust_type(&property.schema_type, config, "");
+                        if rust_type == schema.name {
+                            rust_type = format!("Box<{}>", rust_type);
+                        }
+                        if !property.required {
+                            rust_type = format!("Option<{}>", rust_type);
+                        }
+                        PropertyContext {
+                            renamed: field.trim_start_matches("r#") != property.name,
+                            name: property.name.clone(),
+                            field,
+                            rust_type,
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let mut groups: Vec<OperationGroupContext> = Vec::new();
+        for operation in &contract.operations {
+            let name = operation.tag.clone().unwrap_or_else(|| "Contract".to_string());
+            let context = OperationContext::new(operation, config);
+            match groups.iter_mut().find(|g| g.name == name) {
+                Some(group) => group.push(operation, context),
+                None => {
+                    let mut group = OperationGroupContext::new(name);
+                    group.push(operation, context);
+                    groups.push(group);
+                }
+            }
+        }
+
+        Self {
+            schemas,
+            groups,
+            methods: methods(contract.operations.iter().map(|o| o.method)),
+        }
+    }
+}
+
+impl OperationGroupContext {
+    fn new(name: String) -> Self {
+        Self {
+            module: format!("{}_operations", rust_ident(&name).trim_start_matches("r#")),
+            name,
+            operations: Vec::new(),
+            routes: Vec::new(),
+            methods: Vec::new(),
+            uses_path: false,
+            uses_query: false,
+            uses_json: false,
+            uses_status_code: false,
+            uses_contract: false,
+        }
+    }
+
+    fn push(&mut self, operation: &Operation, context: OperationContext) {
+        let path = axum_path(&operation.path);
+        let handler = RouteHandler {
+            method: context.method.clone(),
+            name: context.name.clone(),
+        };
+        match self.routes.iter_mut().find(|r| r.path == path) {
+            Some(route) => route.handlers.push(handler),
+            None => self.routes.push(RouteContext { path, handlers: vec![handler] }),
+        }
+        if !self.methods.contains(&context.method) {
+            self.methods.push(context.method.clone());
+            self.methods.sort_by_key(|m| method_order(m));
+        }
+
+        self.uses_path |= context.path_binding.is_some();
+        self.uses_query |= context.query;
+        self.uses_json |= context.request_body.is_some() || context.return_type.starts_with("Json");
+        self.uses_status_code |= context.return_type == "StatusCode";
+        let mentions_contract = |ty: &str| ty.contains("contract::");
+        self.uses_contract |= context.params.iter().any(|p| mentions_contract(&p.rust_type))
+            || context.request_body.as_deref().is_some_and(mentions_contract)
+            || context.responses.iter().filter_map(|r| r.body.as_deref()).any(mentions_contract);
+        self.operations.push(context);
+    }
+}
+
+impl OperationContext {
+    fn new(operation: &Operation, config: &ProjectConfig) -> Self {
+        let rust_type = |schema_type: &str| contract_rust_type(schema_type, config, "contract::");
+        let params: Vec<ParamContext> = operation
+            .parameters
+            .iter()
+            .map(|param| {
+                let ty = rust_type(&param.schema_type);
+                ParamContext {
+                    name: param.name.clone(),
+                    rust_type: if param.required || param.location == ParameterLocation::Path {
+                        ty
+                    } else {
+                        format!("Option<{}>", ty)
+                    },
+                    location: format!("{:?}", param.location),
+                    description: escape(if param.description.is_empty() { &param.name } else { &param.description }),
+                }
+            })
+            .collect();
+
+        // Path parameters in the order they appear in the path.
+        let path_params: Vec<(String, String)> = path_parameters(&operation.path)
+            .into_iter()
+            .map(|name| {
+                let ty = operation
+                    .parameters
+                    .iter()
+                    .find(|p| p.location == ParameterLocation::Path && p.name == name)
+                    .map(|p| rust_type(&p.schema_type))
+                    .unwrap_or_else(|| "String".to_string());
+                (format!("_{}", rust_ident(&name).trim_start_matches("r#")), ty)
+            })
+            .collect();
+        let (path_binding, path_type) = match path_params.as_slice() {
+            [] => (None, None),
+            [(binding, ty)] => (Some(binding.clone()), Some(ty.clone())),
+            many => {
+                let join = |f: fn(&(String, String)) -> &String| {
+                    format!("({})", many.iter().map(f).cloned().collect::<Vec<_>>().join(", "))
+                };
+                (Some(join(|p| &p.0)), Some(join(|p| &p.1)))
+            }
+        };
+
+        let responses: Vec<ResponseContext> = operation
+            .responses
+            .iter()
+            .map(|response| ResponseContext {
+                status: response.status,
+                description: escape(&response.description),
+                body: response.body.as_deref().map(rust_type),
+            })
+            .collect();
+        let return_type = responses
+            .iter()
+            .find(|r| (200..300).contains(&r.status))
+            .and_then(|r| r.body.as_ref())
+            .map(|body| format!("Json<{}>", body))
+            .unwrap_or_else(|| "StatusCode".to_string());
+
+        Self {
+            name: operation.name.clone(),
+            operation_id: operation.operation_id.as_deref().map(escape),
+            method: method_name(operation.method).to_string(),
+            path: operation.path.clone(),
+            tag: operation.tag.as_deref().map(escape),
+            summary: operation.summary.as_deref().map(|s| s.replace('\n', " ")),
+            query: params.iter().any(|p| p.location == "Query"),
+            params,
+            path_binding,
+            path_type,
+            request_body: operation.request_body.as_deref().map(rust_type),
+            responses,
+            return_type,
+        }
+    }
+}
+
+/// Renders the templates under `contract/` when the configuration has an API
+/// contract. The handler templates loop over `contract.groups`, one module per
+/// operation group, and the DTOs are rendered once for the whole contract.
+pub async fn generate(config: &ProjectConfig, templates: &TemplateSet, context: &TeraContext, output_dir: &Path, out: &mut OutputTree) -> Result<()> {
+    if config.contract.is_empty() {
+        return Ok(());
+    }
+
+    for relative in templates.paths_in("contract") {
+        for context in templates.contexts(relative, context)? {
+            let path = templates.path(relative, &context)?;
+            let Some(target) = strategy_path(config, path.strip_prefix("contract")?) else {
+                continue;
+            };
+            templates.render_to(relative, output_dir.join(target), &context, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Rust type of a contract type: `Stop[]` -> `Vec<contract::Stop>`,
+/// `Uuid` -> `uuid::Uuid`, `TruckStatus` -> the generated domain enum.
+fn contract_rust_type(schema_type: &str, config: &ProjectConfig, schema_prefix: &str) -> String {
+    if let Some(item) = schema_type.strip_suffix("[]") {
+        return format!("Vec<{}>", contract_rust_type(item, config, schema_prefix));
+    }
+    if schema_type == "Json" {
+        return "serde_json::Value".to_string();
+    }
+    if config.enums.iter().any(|def| def.name == schema_type) {
+        return format!("domain::domain::{}::{}", snake_case(schema_type), schema_type);
+    }
+    match FieldType::from_str(schema_type) {
+        Ok(field_type) => rust_type_of(&field_type).to_string(),
+        Err(_) => format!("{}{}", schema_prefix, schema_type),
+    }
+}
+
+/// Whether any type in the contract is, or contains, the built-in `field_type`.
+pub(crate) fn uses_type(contract: &ApiContract, field_type: &FieldType) -> bool {
+    let is = |schema_type: &str| {
+        FieldType::from_str(schema_type.trim_end_matches("[]")).is_ok_and(|t| &t == field_type)
+    };
+    contract.schemas.iter().flat_map(|s| &s.properties).any(|p| is(&p.schema_type))
+        || contract.operations.iter().any(|o| {
+            o.parameters.iter().any(|p| is(&p.schema_type))
+                || o.request_body.as_deref().is_some_and(is)
+                || o.responses.iter().filter_map(|r| r.body.as_deref()).any(is)
+        })
+}
+
+/// Names of the `{parameters}` in an OpenAPI path, in order.
+pub(crate) fn path_parameters(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+        .map(String::from)
+        .collect()
+}
+
+/// `/trucks/{truckId}` -> `/trucks/:truckId`
+fn axum_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(name) => format!(":{}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn method_name(method: HttpMethod) -> &'static str {
+    match method {
+        HttpMethod::Get => "get",
+        HttpMethod::Post => "post",
+        HttpMethod::Put => "put",
+        HttpMethod::Patch => "patch",
+        HttpMethod::Delete => "delete",
+    }
+}
+
+fn method_order(method: &str) -> usize {
+    ["get", "post", "put", "patch", "delete"].iter().position(|m| *m == method).unwrap_or(usize::MAX)
+}
+
+fn methods(used: impl Iterator<Item = HttpMethod>) -> Vec<String> {
+    let mut methods: Vec<String> = Vec::new();
+    for method in used.map(method_name) {
+        if !methods.iter().any(|m| m == method) {
+            methods.push(method.to_string());
+        }
+    }
+    methods.sort_by_key(|m| method_order(m));
+    methods
+}
+
+
+/// Escapes text for a Rust string literal.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', " ")
+}
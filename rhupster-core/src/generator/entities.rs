@@ -0,0 +1,532 @@
+use crate::config::{Database, Frontend, ProjectConfig, RouterStrategy};
+use crate::model::{Entity, FetchType, Field, FieldType, PrimaryKey, RelationshipKind};
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+use super::contract::uses_type;
+use super::enums::EnumContext;
+use super::naming::{label, pascal_case, pluralize, singularize, snake_case};
+use super::utils::{OutputTree, TemplateSet};
+use tera::Context as TeraContext;
+
+/// Names and per-field details of an entity, exposed to templates as `entity`
+/// (and, for every entity, in the `entities` list).
+#[derive(Debug, Clone, Serialize)]
+pub struct EntityContext {
+    /// PascalCase type name, e.g. `DeliveryRoute`.
+    pub name: String,
+    /// Module and variable name, e.g. `delivery_route`.
+    pub snake: String,
+    /// Table, collection and module name for the collection, e.g. `delivery_routes`.
+    pub plural: String,
+    /// URL segment, e.g. `delivery-routes`.
+    pub route: String,
+    /// Human readable singular and plural, e.g. `Delivery Route` / `Delivery Routes`.
+    pub label: String,
+    pub plural_label: String,
+    pub id: IdContext,
+    pub fields: Vec<FieldContext>,
+    /// Enums used by the fields, once each.
+    pub enums: Vec<EnumContext>,
+    /// Foreign keys held by this entity (many-to-one and one-to-one).
+    pub references: Vec<ReferenceContext>,
+    /// Many-to-many relationships owned by this entity, each backed by a join table.
+    pub joins: Vec<JoinContext>,
+    /// Every other entity the references and joins point at, once each.
+    pub related: Vec<RelatedEntity>,
+    /// Other entities whose repositories the service needs to load eager relationships.
+    pub eager_repositories: Vec<RelatedEntity>,
+    /// Whether reading the entity loads anything besides its own row.
+    pub loads_relations: bool,
+    /// Whether the entity carries the audit columns and records its history.
+    pub audited: bool,
+    /// Whether deleting only sets `deleted_at`, and reads skip rows where it is set.
+    pub soft_delete: bool,
+    /// Whether updates are checked against a `version` column.
+    pub versioned: bool,
+    /// `license_plate, capacity, driver_id`, then the audit columns, `deleted_at` and `version` if enabled.
+    pub columns: String,
+    /// `$1, $2`
+    pub insert_placeholders: String,
+    /// `license_plate = $1, capacity = $2`
+    pub update_assignments: String,
+    /// Placeholder of the id after the updated columns, e.g. `$3`.
+    pub id_placeholder: String,
+    /// Placeholder of the new `version` among the updated columns, if versioned.
+    pub version_placeholder: Option<String>,
+}
+
+/// How an id newtype such as `TruckId` is represented, exposed to templates
+/// as `entity.id` and, for `UserId`, as `user_id`.
+#[derive(Debug, Clone, Serialize)]
+pub struct IdContext {
+    /// Wrapped type, also used in commands, queries and DTOs, e.g. `u64` or `uuid::Uuid`.
+    pub rust_type: String,
+    /// Type of the column in ORM models, e.g. `i64`, or `String` for ids the database can't store natively.
+    pub db_type: String,
+    /// Converts `self.0` to `db_type`.
+    pub to_db: String,
+    /// Converts a `db_type` named `value` back to `rust_type`.
+    pub from_db: String,
+    /// Whether a database sequence assigns the id on insert.
+    pub sequence: bool,
+    /// Expression creating a new id in the application, when there is no sequence.
+    pub generate: Option<String>,
+    pub ts_type: String,
+}
+
+impl IdContext {
+    pub fn new(primary_key: PrimaryKey, database: Database) -> Self {
+        let text = |rust_type: &str, generate: &str| Self {
+            rust_type: rust_type.to_string(),
+            db_type: "String".to_string(),
+            to_db: "self.0.to_string()".to_string(),
+            from_db: "value.parse().expect(\"stored ids are valid\")".to_string(),
+            sequence: false,
+            generate: Some(generate.to_string()),
+            ts_type: "string".to_string(),
+        };
+        let generate = match primary_key {
+            PrimaryKey::UuidV4 => "uuid::Uuid::new_v4()",
+            PrimaryKey::UuidV7 => "uuid::Uuid::now_v7()",
+            PrimaryKey::Ulid => return text("ulid::Ulid", "ulid::Ulid::new()"),
+            PrimaryKey::U64 | PrimaryKey::I64 => {
+                let unsigned = primary_key == PrimaryKey::U64;
+                return Self {
+                    rust_type: if unsigned { "u64" } else { "i64" }.to_string(),
+                    db_type: "i64".to_string(),
+                    to_db: if unsigned { "self.0 as i64" } else { "self.0" }.to_string(),
+                    from_db: if unsigned { "value as u64" } else { "value" }.to_string(),
+                    sequence: true,
+                    generate: None,
+                    ts_type: "number".to_string(),
+                };
+            }
+        };
+        if database == Database::Postgres {
+            Self {
+                db_type: "uuid::Uuid".to_string(),
+                to_db: "self.0".to_string(),
+                from_db: "value".to_string(),
+                ..text("uuid::Uuid", generate)
+            }
+        } else {
+            text("uuid::Uuid", generate)
+        }
+    }
+
+    pub fn of(entity: &str, config: &ProjectConfig) -> Self {
+        Self::new(config.primary_key_of(entity), config.database)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldContext {
+    pub name: String,
+    pub label: String,
+    /// Type used in the domain, commands and DTOs; `Option<_>` unless required.
+    pub rust_type: String,
+    pub required: bool,
+    pub unique: bool,
+    /// Arguments for `#[validate(...)]` derived from min/max, if any.
+    pub validate: Option<String>,
+    pub pattern: Option<String>,
+    /// Name of the static holding the compiled pattern, e.g. `TRUCK_LICENSE_PLATE_PATTERN`.
+    pub pattern_static: String,
+    pub ts_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RelatedEntity {
+    pub name: String,
+    pub snake: String,
+    pub plural: String,
+}
+
+impl RelatedEntity {
+    fn new(name: &str) -> Self {
+        let snake = snake_case(name);
+        Self {
+            name: name.to_string(),
+            plural: pluralize(&snake),
+            snake,
+        }
+    }
+}
+
+/// A foreign key, e.g. `driver` stored as `driver_id` referencing `users`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferenceContext {
+    pub name: String,
+    pub label: String,
+    pub column: String,
+    /// SeaORM column variant, e.g. `DriverId`.
+    pub column_variant: String,
+    pub target: RelatedEntity,
+    /// Type in the domain, e.g. `UserId` or `Option<UserId>` when not required.
+    pub rust_type: String,
+    /// Type in commands, queries and DTOs, e.g. `u64` or `Option<uuid::Uuid>`.
+    pub id_type: String,
+    /// Type of the column in ORM models, e.g. `i64` or `Option<i64>`.
+    pub db_type: String,
+    pub target_id: IdContext,
+    /// SeaORM relation variant, e.g. `Driver`.
+    pub variant: String,
+    /// Whether this is the first reference to its target, which gets the SeaORM `Related` impl.
+    pub first_to_target: bool,
+    pub required: bool,
+    /// One-to-one references are unique.
+    pub unique: bool,
+    pub eager: bool,
+}
+
+/// A many-to-many relationship, e.g. `delivery_route` stored in `truck_delivery_routes`.
+#[derive(Debug, Clone, Serialize)]
+pub struct JoinContext {
+    pub name: String,
+    pub plural: String,
+    pub target: RelatedEntity,
+    pub table: String,
+    pub owner_column: String,
+    pub target_column: String,
+    pub owner_variant: String,
+    pub target_variant: String,
+    /// Ids of the owner and of the target.
+    pub owner_id: IdContext,
+    pub target_id: IdContext,
+    pub eager: bool,
+}
+
+/// Columns every audited entity gets after its fields and references, with
+/// whether each is required.
+pub(crate) const AUDIT_COLUMNS: [(&str, FieldType, bool); 4] = [
+    ("created_by", FieldType::String, false),
+    ("created_date", FieldType::DateTime, true),
+    ("last_modified_by", FieldType::String, false),
+    ("last_modified_date", FieldType::DateTime, true),
+];
+
+/// Nullable timestamp column of soft-deleted entities, set when a row is deleted.
+pub(crate) const SOFT_DELETE_COLUMN: &str = "deleted_at";
+
+/// Integer column of versioned entities, bumped by every save.
+pub(crate) const VERSION_COLUMN: &str = "version";
+
+/// Which optional crates the entities' field and id types pull into the generated workspace.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DomainUses {
+    pub uuid: bool,
+    /// Whether some id is a UUID v7, which needs the `v7` feature of `uuid`.
+    pub uuid_v7: bool,
+    pub ulid: bool,
+    /// Whether some field is a Decimal, which needs `rust_decimal` and the ORM's decimal feature.
+    pub decimal: bool,
+    pub datetime: bool,
+    pub pattern: bool,
+    pub enums: bool,
+    /// Whether some entity is audited, which adds the `entity_audit` history.
+    pub audit: bool,
+    /// Whether some entity is versioned, which adds the `If-Match` extractor.
+    pub versioning: bool,
+}
+
+impl DomainUses {
+    pub fn of(config: &ProjectConfig) -> Self {
+        let fields = || config.entities.iter().flat_map(|e| &e.fields);
+        let keys = || std::iter::once(config.primary_key).chain(config.entities.iter().map(|e| config.primary_key_of(&e.name)));
+        Self {
+            uuid: fields().any(|f| f.field_type == FieldType::Uuid)
+                || uses_type(&config.contract, &FieldType::Uuid)
+                || keys().any(|k| matches!(k, PrimaryKey::UuidV4 | PrimaryKey::UuidV7)),
+            uuid_v7: keys().any(|k| k == PrimaryKey::UuidV7),
+            ulid: keys().any(|k| k == PrimaryKey::Ulid),
+            decimal: fields().any(|f| f.field_type == FieldType::Decimal) || uses_type(&config.contract, &FieldType::Decimal),
+            datetime: fields().any(|f| f.field_type == FieldType::DateTime)
+                || config.entities.iter().any(|e| e.audited || e.soft_delete),
+            pattern: fields().any(|f| f.pattern.is_some()),
+            enums: !config.enums.is_empty(),
+            audit: config.entities.iter().any(|e| e.audited),
+            versioning: config.entities.iter().any(|e| e.versioned),
+        }
+    }
+}
+
+impl EntityContext {
+    pub fn new(entity: &Entity, config: &ProjectConfig) -> Self {
+        let snake = snake_case(&entity.name);
+        let plural = pluralize(&snake);
+        let (singular_label, plural_label) = (label(&snake), label(&plural));
+        let id = IdContext::of(&entity.name, config);
+        let fields: Vec<FieldContext> = entity.fields.iter().map(|f| FieldContext::new(&snake, f, config)).collect();
+        let enums = config
+            .enums
+            .iter()
+            .filter(|def| entity.fields.iter().any(|f| f.field_type == FieldType::Enum(def.name.clone())))
+            .map(|def| EnumContext::new(def, config))
+            .collect();
+
+        let mut references = Vec::new();
+        let mut joins = Vec::new();
+        for relationship in config.relationships.iter().filter(|r| r.owner() == entity.name) {
+            let target = RelatedEntity::new(relationship.target());
+            let name = relationship.owner_field().map(String::from).unwrap_or_else(|| target.snake.clone());
+            let eager = relationship.fetch == FetchType::Eager;
+            let target_id = IdContext::of(&target.name, config);
+            if relationship.kind == RelationshipKind::ManyToMany {
+                // The field may already be plural (`routes`); pluralising it again
+                // would name things `set_routeses`.
+                let (name, name_plural) = if singularize(&name) != name && pluralize(&singularize(&name)) == name {
+                    (singularize(&name), name)
+                } else {
+                    let plural = pluralize(&name);
+                    (name, plural)
+                };
+                let owner_column = format!("{}_id", snake);
+                let target_column = match format!("{}_id", target.snake) {
+                    column if column == owner_column => format!("{}_id", name),
+                    column => column,
+                };
+                // Named after the target unless the owner links to it more than one way.
+                let table = if name == target.snake {
+                    format!("{}_{}", snake, target.plural)
+                } else {
+                    format!("{}_{}", snake, name_plural)
+                };
+                joins.push(JoinContext {
+                    table,
+                    owner_variant: pascal_case(&owner_column),
+                    target_variant: pascal_case(&target_column),
+                    owner_id: id.clone(),
+                    target_id,
+                    owner_column,
+                    target_column,
+                    plural: name_plural,
+                    name,
+                    target,
+                    eager,
+                });
+            } else {
+                let column = format!("{}_id", name);
+                let optional = |ty: String| if relationship.required { ty } else { format!("Option<{}>", ty) };
+                references.push(ReferenceContext {
+                    rust_type: optional(format!("{}Id", target.name)),
+                    id_type: optional(target_id.rust_type.clone()),
+                    db_type: optional(target_id.db_type.clone()),
+                    target_id,
+                    variant: pascal_case(&name),
+                    first_to_target: !references.iter().any(|r: &ReferenceContext| r.target == target),
+                    label: label(&name),
+                    column_variant: pascal_case(&column),
+                    column,
+                    name,
+                    target,
+                    required: relationship.required,
+                    unique: relationship.kind == RelationshipKind::OneToOne,
+                    eager,
+                });
+            }
+        }
+
+        let mut related = Vec::new();
+        let mut eager_repositories = Vec::new();
+        let targets = references
+            .iter()
+            .map(|r| (&r.target, r.eager))
+            .chain(joins.iter().map(|j| (&j.target, j.eager)));
+        for (target, eager) in targets.filter(|(t, _)| t.name != entity.name) {
+            if !related.contains(target) {
+                related.push(target.clone());
+            }
+            if eager && !eager_repositories.contains(target) {
+                eager_repositories.push(target.clone());
+            }
+        }
+
+        let column_names: Vec<&str> = fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .chain(references.iter().map(|r| r.column.as_str()))
+            .chain(AUDIT_COLUMNS.iter().filter(|_| entity.audited).map(|(column, _, _)| *column))
+            .chain(entity.soft_delete.then_some(SOFT_DELETE_COLUMN))
+            .chain(entity.versioned.then_some(VERSION_COLUMN))
+            .collect();
+        let columns = column_names.join(", ");
+        let insert_placeholders = (1..=column_names.len()).map(|i| format!("${}", i)).collect::<Vec<_>>().join(", ");
+        let update_assignments = column_names
+            .iter()
+            .enumerate()
+            .map(|(i, c)| format!("{} = ${}", c, i + 1))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Self {
+            name: entity.name.clone(),
+            route: plural.replace('_', "-"),
+            id_placeholder: format!("${}", column_names.len() + 1),
+            version_placeholder: entity.versioned.then(|| format!("${}", column_names.len())),
+            snake,
+            plural,
+            label: singular_label,
+            plural_label,
+            id,
+            fields,
+            enums,
+            loads_relations: !joins.is_empty() || references.iter().any(|r| r.eager),
+            audited: entity.audited,
+            soft_delete: entity.soft_delete,
+            versioned: entity.versioned,
+            references,
+            joins,
+            related,
+            eager_repositories,
+            columns,
+            insert_placeholders,
+            update_assignments,
+        }
+    }
+}
+
+/// Rust type of a field type in the domain, commands and DTOs.
+pub(crate) fn rust_type_of(field_type: &FieldType) -> &str {
+    match field_type {
+        FieldType::String => "String",
+        FieldType::Integer => "i32",
+        FieldType::Long => "i64",
+        FieldType::Float => "f32",
+        FieldType::Double => "f64",
+        FieldType::Decimal => "rust_decimal::Decimal",
+        FieldType::Boolean => "bool",
+        FieldType::Uuid => "uuid::Uuid",
+        FieldType::DateTime => "chrono::DateTime<chrono::Utc>",
+        FieldType::Enum(name) => name,
+    }
+}
+
+impl FieldContext {
+    fn new(entity_snake: &str, field: &Field, config: &ProjectConfig) -> Self {
+        let base_type = rust_type_of(&field.field_type);
+        let rust_type = if field.required {
+            base_type.to_string()
+        } else {
+            format!("Option<{}>", base_type)
+        };
+
+        let bounds = |float: bool| {
+            let literal = |v: i64| if float { format!("{}.0", v) } else { v.to_string() };
+            let mut args = Vec::new();
+            if let Some(min) = field.min {
+                args.push(format!("min = {}", literal(min)));
+            }
+            if let Some(max) = field.max {
+                args.push(format!("max = {}", literal(max)));
+            }
+            args.join(", ")
+        };
+        let validate = match &field.field_type {
+            _ if field.min.is_none() && field.max.is_none() => None,
+            FieldType::String => Some(format!("length({})", bounds(false))),
+            FieldType::Float | FieldType::Double => Some(format!("range({})", bounds(true))),
+            // `range` compares through `as f64`, which a Decimal does not support.
+            FieldType::Decimal => None,
+            t if t.is_numeric() => Some(format!("range({})", bounds(false))),
+            _ => None,
+        };
+
+        let ts_type = match &field.field_type {
+            FieldType::Boolean => "boolean".to_string(),
+            FieldType::Enum(name) => match config.enums.iter().find(|def| &def.name == name) {
+                Some(def) => EnumContext::new(def, config).ts_type,
+                None => "string".to_string(),
+            },
+            // rust_decimal serializes as a string so no precision is lost in JSON.
+            FieldType::Decimal => "string".to_string(),
+            t if t.is_numeric() => "number".to_string(),
+            _ => "string".to_string(),
+        };
+
+        Self {
+            name: field.name.clone(),
+            label: label(&field.name),
+            rust_type,
+            required: field.required,
+            unique: field.unique,
+            validate,
+            pattern: field.pattern.clone(),
+            pattern_static: format!("{}_{}_PATTERN", entity_snake, field.name).to_uppercase(),
+            ts_type,
+        }
+    }
+}
+
+/// Renders the templates under `entity/` once per entity in the configuration,
+/// with the entity in the context, so `core/src/domain/{{ entity.snake }}.rs.tera`
+/// becomes `core/src/domain/truck.rs`. Templates under
+/// `api/router_strategies/<strategy>/` and `frontend/<framework>/` are only
+/// rendered for the selected options.
+pub async fn generate(config: &ProjectConfig, templates: &TemplateSet, context: &TeraContext, output_dir: &Path, out: &mut OutputTree) -> Result<()> {
+    for entity in &config.entities {
+        let entity_context = EntityContext::new(entity, config);
+        let mut context = context.clone();
+        context.insert("entity", &entity_context);
+
+        for relative in templates.paths_in("entity") {
+            for context in templates.contexts(relative, &context)? {
+                let path = templates.path(relative, &context)?;
+                let Some(target) = strategy_path(config, path.strip_prefix("entity")?) else {
+                    continue;
+                };
+                templates.render_to(relative, output_dir.join(target), &context, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Where a per-item template ends up in the generated project, or `None` if it
+/// belongs to a router strategy or frontend that was not selected.
+pub(crate) fn strategy_path(config: &ProjectConfig, relative: &Path) -> Option<PathBuf> {
+    let components: Vec<String> = relative
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(s) => Some(s.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+
+    let (prefix, rest) = match components.as_slice() {
+        [api, strategies, strategy, rest @ ..] if api == "api" && strategies == "router_strategies" => {
+            if strategy != router_strategy_dir(config.router_strategy) {
+                return None;
+            }
+            (vec!["api".to_string()], rest)
+        }
+        [frontend, framework, rest @ ..] if frontend == "frontend" => {
+            if Some(framework.as_str()) != frontend_dir(config.frontend) {
+                return None;
+            }
+            (vec!["api".to_string(), "client".to_string()], rest)
+        }
+        rest => (Vec::new(), rest),
+    };
+
+    Some(prefix.iter().chain(rest).collect())
+}
+
+fn router_strategy_dir(strategy: RouterStrategy) -> &'static str {
+    match strategy {
+        RouterStrategy::Standard => "standard",
+        RouterStrategy::AxumController => "axum_controller",
+        RouterStrategy::AxumFolderRouter => "axum_folder_router",
+    }
+}
+
+fn frontend_dir(frontend: Frontend) -> Option<&'static str> {
+    match frontend {
+        Frontend::React => Some("react"),
+        Frontend::Vue => Some("vue"),
+        Frontend::Svelte => Some("svelte"),
+        Frontend::Angular => Some("angular"),
+        Frontend::None => None,
+    }
+}
+
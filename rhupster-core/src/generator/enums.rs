@@ -0,0 +1,86 @@
+use crate::config::ProjectConfig;
+use crate::model::{EnumDef, FieldType};
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+use super::naming::{pascal_case, snake_case};
+use super::utils::{OutputTree, TemplateSet};
+use tera::Context as TeraContext;
+
+/// A declared enum, exposed to templates as `enum` (and, for every enum, in
+/// the `enums` list).
+#[derive(Debug, Clone, Serialize)]
+pub struct EnumContext {
+    /// PascalCase type name, e.g. `TruckStatus`.
+    pub name: String,
+    /// Module name and database type name, e.g. `truck_status`.
+    pub snake: String,
+    pub variants: Vec<VariantContext>,
+    /// `'AVAILABLE', 'IN_TRANSIT'`, for `ENUM (...)` and `CHECK (... IN (...))`.
+    pub sql_values: String,
+    /// `'AVAILABLE' | 'IN_TRANSIT'`
+    pub ts_type: String,
+    /// Whether any entity field has this type.
+    pub used: bool,
+}
+
+/// A variant, e.g. `InTransit` stored and serialized as `IN_TRANSIT`.
+#[derive(Debug, Clone, Serialize)]
+pub struct VariantContext {
+    pub name: String,
+    /// Escaped for a string literal.
+    pub value: String,
+}
+
+impl EnumContext {
+    pub fn new(def: &EnumDef, config: &ProjectConfig) -> Self {
+        let quoted = |sep: &str, quote: &str| {
+            def.values
+                .iter()
+                .map(|v| format!("'{}'", def.wire_value(v).replace('\'', quote)))
+                .collect::<Vec<_>>()
+                .join(sep)
+        };
+        Self {
+            name: def.name.clone(),
+            snake: snake_case(&def.name),
+            variants: def
+                .values
+                .iter()
+                .map(|value| VariantContext {
+                    name: variant_name(value),
+                    value: def.wire_value(value).replace('\\', "\\\\").replace('"', "\\\""),
+                })
+                .collect(),
+            sql_values: quoted(", ", "''"),
+            ts_type: quoted(" | ", "\\'"),
+            used: config
+                .entities
+                .iter()
+                .flat_map(|e| &e.fields)
+                .any(|f| f.field_type == FieldType::Enum(def.name.clone())),
+        }
+    }
+}
+
+/// Renders the templates under `enum/` once per declared enum, with the enum
+/// in the context, so `core/src/domain/{{ enum.snake }}.rs.tera` becomes
+/// `core/src/domain/truck_status.rs`.
+pub async fn generate(config: &ProjectConfig, templates: &TemplateSet, context: &TeraContext, output_dir: &Path, out: &mut OutputTree) -> Result<()> {
+    for def in &config.enums {
+        let enum_context = EnumContext::new(def, config);
+        let mut context = context.clone();
+        context.insert("enum", &enum_context);
+        templates.render_dir("enum", output_dir, &context, None, out)?;
+    }
+    Ok(())
+}
+
+/// `IN_TRANSIT` -> `InTransit`, `available` -> `Available`, `OnHold` stays as is.
+fn variant_name(value: &str) -> String {
+    if value.contains('_') || !value.chars().any(|c| c.is_ascii_lowercase()) {
+        pascal_case(&value.to_ascii_lowercase())
+    } else {
+        pascal_case(value)
+    }
+}
@@ -0,0 +1,148 @@
+//! Template directories fetched from a git repository, given on the command
+//! line as `git+<url>#<rev>`:
+//!
+//! ```text
+//! git+https://github.com/acme/rhupster-templates#v1.2.0
+//! git+file:///home/me/templates#main
+//! ```
+//!
+//! Repositories are mirrored under a cache directory and every resolved commit
+//! is checked out once, next to the mirror, so later runs at the same commit
+//! work offline. Fetching uses the `git` command line, with the user's
+//! credentials and configuration.
+
+use anyhow::{bail, Context, Result};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Prefix that marks a `--templates` value as a git repository.
+pub const SPEC_PREFIX: &str = "git+";
+
+/// A commit of a template repository checked out on disk.
+#[derive(Debug, Clone)]
+pub struct Checkout {
+    pub dir: PathBuf,
+    /// Full hash of the commit `rev` resolved to.
+    pub commit: String,
+}
+
+/// Splits `git+<url>#<rev>` into its url and revision; the revision defaults
+/// to the repository's `HEAD`. Anything without the `git+` prefix is not a spec.
+pub fn parse_spec(spec: &str) -> Option<(&str, &str)> {
+    let spec = spec.strip_prefix(SPEC_PREFIX)?;
+    match spec.rsplit_once('#') {
+        Some((url, rev)) if !rev.is_empty() => Some((url, rev)),
+        Some((url, _)) => Some((url, "HEAD")),
+        None => Some((spec, "HEAD")),
+    }
+}
+
+/// Resolves `rev` of the repository at `url` to a commit and checks it out
+/// below `cache_dir`, fetching the repository first unless `rev` is a commit
+/// that is already there. A failed fetch is only an error when `rev` cannot be
+/// resolved from what was fetched before.
+pub fn checkout(url: &str, rev: &str, cache_dir: &Path) -> Result<Checkout> {
+    let slug = slug(url);
+    let mirror = cache_dir.join("repos").join(&slug);
+
+    if !mirror.join("HEAD").exists() {
+        std::fs::create_dir_all(cache_dir.join("repos"))
+            .with_context(|| format!("Failed to create the template cache {}", cache_dir.display()))?;
+        git(None, ["clone", "--quiet", "--mirror", "--", url].map(OsStr::new).into_iter().chain([mirror.as_os_str()]))
+            .with_context(|| format!("Failed to clone template repository {}", url))?;
+    } else if !is_commit_hash(rev) || resolve(&mirror, rev).is_none() {
+        if let Err(err) = git(Some(&mirror), ["fetch", "--quiet", "--prune", "origin"]) {
+            if resolve(&mirror, rev).is_none() {
+                return Err(err.context(format!("Failed to fetch template repository {}", url)));
+            }
+        }
+    }
+
+    let Some(commit) = resolve(&mirror, rev) else {
+        bail!("Template repository {} has no revision '{}'", url, rev);
+    };
+    let dir = cache_dir.join("checkouts").join(&slug).join(&commit);
+    if !dir.exists() {
+        export(&mirror, &commit, &dir).with_context(|| format!("Failed to check out {} of template repository {}", commit, url))?;
+    }
+    Ok(Checkout { dir, commit })
+}
+
+/// Writes the files of `commit` to `dir`, without a `.git` entry, through a
+/// private index so the mirror is left untouched. The files are moved into
+/// place once complete, so an interrupted checkout is never reused.
+fn export(mirror: &Path, commit: &str, dir: &Path) -> Result<()> {
+    let partial = dir.with_extension("partial");
+    let index = dir.with_extension("index");
+    let _ = std::fs::remove_dir_all(&partial);
+    let _ = std::fs::remove_file(&index);
+    std::fs::create_dir_all(&partial)?;
+
+    let git = || {
+        let mut command = command(Some(mirror));
+        command.arg("--work-tree").arg(&partial).env("GIT_INDEX_FILE", &index);
+        command
+    };
+    let result = run(git().args(["read-tree", commit])).and_then(|_| run(git().args(["checkout-index", "--all", "--force"])));
+    let _ = std::fs::remove_file(&index);
+    result?;
+    std::fs::rename(&partial, dir)?;
+    Ok(())
+}
+
+fn resolve(repo: &Path, rev: &str) -> Option<String> {
+    git(Some(repo), ["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", rev)]).ok()
+}
+
+fn is_commit_hash(rev: &str) -> bool {
+    rev.len() == 40 && rev.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Directory name of a repository in the cache: `https://github.com/acme/templates`
+/// -> `github.com_acme_templates-1a2b3c4d`. The readable part can be shared by
+/// different URLs, like `a/x_y` and `a/x/y`, so a hash of the full URL follows.
+fn slug(url: &str) -> String {
+    let readable = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let readable: String = readable
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+        .collect();
+    format!("{}-{:08x}", readable.trim_matches(|c| c == '_' || c == '.'), fnv1a(url) as u32)
+}
+
+/// 64-bit FNV-1a, which unlike `DefaultHasher` stays the same across Rust
+/// releases, so the cache keeps its layout.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Runs git in `repo` and returns its trimmed standard output.
+fn git<I, S>(repo: Option<&Path>, args: I) -> Result<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    run(command(repo).args(args))
+}
+
+/// A git command for `repo` that never prompts for credentials.
+fn command(repo: Option<&Path>) -> Command {
+    let mut command = Command::new("git");
+    if let Some(repo) = repo {
+        command.arg("--git-dir").arg(repo);
+    }
+    command.env("GIT_TERMINAL_PROMPT", "0");
+    command
+}
+
+/// Runs `command`, failing with its standard error.
+fn run(command: &mut Command) -> Result<String> {
+    let output = command.output().context("Failed to run git; is it installed?")?;
+    if !output.status.success() {
+        bail!("git failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
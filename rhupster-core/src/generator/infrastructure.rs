@@ -1,9 +1,10 @@
 use crate::config::ProjectConfig;
 use anyhow::Result;
 use std::path::Path;
-use super::utils::{render_file_from_template, TemplateSource};
+use tera::Context as TeraContext;
+use super::utils::{OutputTree, TemplateSet};
 
-pub async fn generate(config: &ProjectConfig, template_root: TemplateSource<'_>, output_dir: &Path) -> Result<()> {
+pub async fn generate(config: &ProjectConfig, templates: &TemplateSet, context: &TeraContext, output_dir: &Path, out: &mut OutputTree) -> Result<()> {
      if !config.devops.docker_compose {
         return Ok(());
     }
@@ -11,8 +12,8 @@ pub async fn generate(config: &ProjectConfig, template_root: TemplateSource<'_>,
 
     let template_path = "infrastructure/docker-compose.yml.tera";
     
-    if template_root.has_file(template_path) {
-        render_file_from_template(config, template_root, template_path, output_dir.join("docker-compose.yml")).await?;
+    if templates.contains(template_path) {
+        templates.render_to(Path::new(template_path), output_dir.join("docker-compose.yml"), context, out)?;
     }
 
     Ok(())
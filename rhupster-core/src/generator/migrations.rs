@@ -0,0 +1,508 @@
+use crate::config::{Database, Orm, ProjectConfig};
+use crate::model::{FieldType, PrimaryKey};
+use crate::manifest::{EnumTypeSchema, SchemaHistory, TableSchema};
+use anyhow::Result;
+use chrono::Utc;
+use serde::Serialize;
+use std::path::Path;
+use tera::Context as TeraContext;
+use super::entities::{EntityContext, AUDIT_COLUMNS, SOFT_DELETE_COLUMN, VERSION_COLUMN};
+use super::naming::snake_case;
+use super::enums::EnumContext;
+use super::utils::{OutputTree, TemplateSet};
+
+/// Name of the first migration of every project, creating the whole schema.
+const INITIAL_MIGRATION: &str = "00000000000001_create_tables";
+
+/// Keywords starting a constraint rather than a column definition.
+const CONSTRAINTS: [&str; 5] = ["PRIMARY KEY", "FOREIGN KEY", "UNIQUE", "CHECK", "CONSTRAINT"];
+
+/// A table of the generated schema, exposed to templates in the `tables` list.
+#[derive(Debug, Clone, Serialize)]
+pub struct TableContext {
+    pub name: String,
+    /// Column and constraint definitions in the selected database's dialect.
+    pub definitions: Vec<String>,
+    /// `id`, or both columns of a join table.
+    pub primary_key: Vec<String>,
+    pub diesel_columns: Vec<DieselColumn>,
+    /// Extra `use` lines inside Diesel's `table!`, for enum column types.
+    pub diesel_imports: Vec<String>,
+    pub foreign_keys: Vec<ForeignKey>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DieselColumn {
+    pub name: String,
+    pub sql_type: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ForeignKey {
+    pub column: String,
+    pub table: String,
+    /// Whether Diesel gets a `joinable!` for it; only one is allowed per pair
+    /// of distinct tables.
+    pub joinable: bool,
+}
+
+/// Renders the SQL migrations (and, for Diesel, `schema.rs`) of the users
+/// table, one table per entity and one join table per many-to-many relationship.
+/// SeaORM projects get the sqlx layout plus a `migration` crate running it, so
+/// `sea-orm-cli migrate up` creates the same schema. MongoDB has no schema.
+///
+/// The first run creates the whole schema. Later runs leave the migrations of
+/// `history` alone and add one for what changed since, if anything; the
+/// returned history is the one to record in the manifest. Projects generated
+/// before migrations were recorded get their first migration rendered again.
+pub async fn generate(config: &ProjectConfig, history: &SchemaHistory, templates: &TemplateSet, context: &TeraContext, output_dir: &Path, out: &mut OutputTree) -> Result<SchemaHistory> {
+    let migrations_dir = output_dir.join("infrastructure/migrations");
+    let paths = |name: &str| match config.orm {
+        Orm::Diesel => (migrations_dir.join(name).join("up.sql"), migrations_dir.join(name).join("down.sql")),
+        _ => (migrations_dir.join(format!("{}.up.sql", name)), migrations_dir.join(format!("{}.down.sql", name))),
+    };
+    if config.orm == Orm::None {
+        return Ok(history.clone());
+    }
+
+    let current = schema(config);
+    let migrations = if history.is_empty() {
+        let (up, down) = paths(INITIAL_MIGRATION);
+        templates.render_to(Path::new("migrations/up.sql.tera"), up, context, out)?;
+        templates.render_to(Path::new("migrations/down.sql.tera"), down, context, out)?;
+        vec![INITIAL_MIGRATION.to_string()]
+    } else if history.tables != current.tables || history.enum_types != current.enum_types {
+        let name = format!("{}_update_schema", next_version(history));
+        let (up, down) = paths(&name);
+        for (target, from, to, revert) in [(up, history, &current, false), (down, &current, history, true)] {
+            let mut context = context.clone();
+            context.insert("statements", &changes(from, to, config.database));
+            context.insert("revert", &revert);
+            templates.render_to(Path::new("migrations/update.sql.tera"), target, &context, out)?;
+        }
+        history.migrations.iter().cloned().chain([name]).collect()
+    } else {
+        history.migrations.clone()
+    };
+
+    match config.orm {
+        Orm::Diesel => {
+            let schema = output_dir.join("infrastructure/src/persistence/schema.rs");
+            templates.render_to(Path::new("migrations/schema.rs.tera"), schema, context, out)?;
+        }
+        Orm::SeaOrm => {
+            let mut context = context.clone();
+            context.insert("migrations", &migrations);
+            templates.render_dir("migrations/seaorm", &output_dir.join("migration"), &context, None, out)?;
+        }
+        _ => {}
+    }
+    Ok(SchemaHistory { migrations, ..current })
+}
+
+/// The tables and Postgres enum types of the configured schema, as recorded
+/// in the manifest.
+pub fn schema(config: &ProjectConfig) -> SchemaHistory {
+    let enum_types = match config.database {
+        Database::Postgres => config
+            .enums
+            .iter()
+            .map(|def| EnumTypeSchema {
+                name: snake_case(&def.name),
+                values: def.values.iter().map(|v| format!("'{}'", def.wire_value(v).replace('\'', "''"))).collect(),
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    SchemaHistory {
+        migrations: Vec::new(),
+        tables: tables(config).into_iter().map(|t| TableSchema { name: t.name, definitions: t.definitions }).collect(),
+        enum_types,
+    }
+}
+
+/// A timestamp version, after that of the last migration even if the clock
+/// says otherwise.
+fn next_version(history: &SchemaHistory) -> String {
+    let now: u64 = Utc::now().format("%Y%m%d%H%M%S").to_string().parse().expect("a timestamp is a number");
+    let last = history
+        .migrations
+        .last()
+        .and_then(|name| name.split('_').next())
+        .and_then(|version| version.parse::<u64>().ok())
+        .unwrap_or(0);
+    format!("{:014}", now.max(last + 1))
+}
+
+/// The statements turning the schema `from` into `to`: enum types and tables
+/// are created first, then columns and constraints added or dropped, then
+/// tables and types dropped. Changes SQL cannot express without knowing the
+/// data, such as a column changing type, are left as comments to complete.
+fn changes(from: &SchemaHistory, to: &SchemaHistory, dialect: Database) -> Vec<String> {
+    let mut statements = Vec::new();
+    for enum_type in &to.enum_types {
+        match from.enum_types.iter().find(|e| e.name == enum_type.name) {
+            None => statements.push(format!("CREATE TYPE {} AS ENUM ({});", enum_type.name, enum_type.values.join(", "))),
+            Some(old) => {
+                for value in enum_type.values.iter().filter(|v| !old.values.contains(v)) {
+                    statements.push(format!("ALTER TYPE {} ADD VALUE {};", enum_type.name, value));
+                }
+                for value in old.values.iter().filter(|v| !enum_type.values.contains(v)) {
+                    statements.push(format!(
+                        "-- TODO: {} no longer has the value {}; Postgres cannot drop it, so update the rows using it and recreate the type.",
+                        enum_type.name, value
+                    ));
+                }
+            }
+        }
+    }
+    for table in &to.tables {
+        let Some(old) = from.tables.iter().find(|t| t.name == table.name) else {
+            statements.push(format!("CREATE TABLE IF NOT EXISTS {} (\n    {}\n);", table.name, table.definitions.join(",\n    ")));
+            continue;
+        };
+        for definition in table.definitions.iter().filter(|d| !old.definitions.contains(d)) {
+            if is_constraint(definition) {
+                statements.push(match dialect {
+                    Database::SQLite => format!("-- TODO: add `{}` to {}; SQLite only adds constraints by rebuilding the table.", definition, table.name),
+                    _ => format!("ALTER TABLE {} ADD {};", table.name, definition),
+                });
+                continue;
+            }
+            let column = column_name(definition);
+            match old.definitions.iter().find(|d| !is_constraint(d) && column_name(d) == column) {
+                None => statements.push(format!("ALTER TABLE {} ADD COLUMN {};", table.name, definition)),
+                Some(previous) => statements.push(format!(
+                    "-- TODO: {}.{} changed from `{}` to `{}`; alter the column to match.",
+                    table.name, column, previous, definition
+                )),
+            }
+        }
+        for definition in old.definitions.iter().filter(|d| !table.definitions.contains(d)) {
+            if is_constraint(definition) {
+                statements.push(format!("-- TODO: drop `{}` from {}.", definition, table.name));
+            } else if !table.definitions.iter().any(|d| !is_constraint(d) && column_name(d) == column_name(definition)) {
+                statements.push(format!("ALTER TABLE {} DROP COLUMN {};", table.name, column_name(definition)));
+            }
+        }
+    }
+    for table in from.tables.iter().rev().filter(|t| !to.tables.iter().any(|n| n.name == t.name)) {
+        statements.push(format!("DROP TABLE IF EXISTS {};", table.name));
+    }
+    for enum_type in from.enum_types.iter().rev().filter(|e| !to.enum_types.iter().any(|n| n.name == e.name)) {
+        statements.push(format!("DROP TYPE IF EXISTS {};", enum_type.name));
+    }
+    statements
+}
+
+fn is_constraint(definition: &str) -> bool {
+    CONSTRAINTS.iter().any(|keyword| definition.starts_with(keyword))
+}
+
+fn column_name(definition: &str) -> &str {
+    definition.split_whitespace().next().unwrap_or(definition)
+}
+
+/// Every table in creation order: users, then entities so that referenced
+/// tables come first, then join tables, then `entity_audit` if some entity
+/// is audited.
+pub fn tables(config: &ProjectConfig) -> Vec<TableContext> {
+    let dialect = config.database;
+    let mut tables = vec![TableContext {
+        name: "users".to_string(),
+        definitions: vec![
+            id_column(dialect, config.primary_key),
+            format!("username {} NOT NULL UNIQUE", string_type(dialect)),
+            format!("email {} NOT NULL UNIQUE", string_type(dialect)),
+            format!("password_hash {} NOT NULL", string_type(dialect)),
+        ],
+        primary_key: vec!["id".to_string()],
+        diesel_columns: vec![
+            diesel_column("id", key_diesel_type(config.primary_key, dialect), true),
+            diesel_column("username", "Text", true),
+            diesel_column("email", "Text", true),
+            diesel_column("password_hash", "Text", true),
+        ],
+        diesel_imports: Vec::new(),
+        foreign_keys: Vec::new(),
+    }];
+
+    let entities: Vec<EntityContext> = config.entities.iter().map(|e| EntityContext::new(e, config)).collect();
+    let mut join_tables = Vec::new();
+    for entity in creation_order(&entities) {
+        let key = config.primary_key_of(&entity.name);
+        let mut definitions = vec![id_column(dialect, key)];
+        let mut diesel_columns = vec![diesel_column("id", key_diesel_type(key, dialect), true)];
+        let mut diesel_imports = Vec::new();
+        let mut foreign_keys = Vec::new();
+        let model = config.entities.iter().find(|e| e.name == entity.name).expect("context built from config");
+        for field in &model.fields {
+            let mut definition = format!("{} {}", field.name, sql_type(&field.field_type, dialect, config));
+            if field.required {
+                definition.push_str(" NOT NULL");
+            }
+            if field.unique {
+                definition.push_str(" UNIQUE");
+            }
+            let diesel_type = match &field.field_type {
+                FieldType::Enum(name) => {
+                    let def = config.enums.iter().find(|e| &e.name == name);
+                    if let (Database::SQLite, Some(def)) = (dialect, def) {
+                        let values = EnumContext::new(def, config).sql_values;
+                        definition.push_str(&format!(" CHECK ({} IN ({}))", field.name, values));
+                    }
+                    let import = format!("use domain::domain::{}::{}Mapping;", snake_case(name), name);
+                    if !diesel_imports.contains(&import) {
+                        diesel_imports.push(import);
+                    }
+                    format!("{}Mapping", name)
+                }
+                field_type => diesel_type(field_type, dialect).to_string(),
+            };
+            definitions.push(definition);
+            diesel_columns.push(diesel_column(&field.name, &diesel_type, field.required));
+        }
+        let mut constraints = Vec::new();
+        for reference in &entity.references {
+            let target_key = config.primary_key_of(&reference.target.name);
+            let mut definition = format!("{} {}", reference.column, key_sql_type(target_key, dialect));
+            if reference.required {
+                definition.push_str(" NOT NULL");
+            }
+            if reference.unique {
+                definition.push_str(" UNIQUE");
+            }
+            definitions.push(definition);
+            constraints.push(format!(
+                "FOREIGN KEY ({}) REFERENCES {}(id) ON DELETE {}",
+                reference.column,
+                reference.target.plural,
+                if reference.required { "CASCADE" } else { "SET NULL" }
+            ));
+            diesel_columns.push(diesel_column(&reference.column, key_diesel_type(target_key, dialect), reference.required));
+            foreign_keys.push(ForeignKey {
+                column: reference.column.clone(),
+                table: reference.target.plural.clone(),
+                joinable: false,
+            });
+        }
+        if entity.audited {
+            for (column, field_type, required) in &AUDIT_COLUMNS {
+                let null = if *required { " NOT NULL" } else { "" };
+                definitions.push(format!("{} {}{}", column, sql_type(field_type, dialect, config), null));
+                diesel_columns.push(diesel_column(column, diesel_type(field_type, dialect), *required));
+            }
+        }
+        if entity.soft_delete {
+            definitions.push(format!("{} {}", SOFT_DELETE_COLUMN, sql_type(&FieldType::DateTime, dialect, config)));
+            diesel_columns.push(diesel_column(SOFT_DELETE_COLUMN, diesel_type(&FieldType::DateTime, dialect), false));
+        }
+        if entity.versioned {
+            definitions.push(format!("{} {} NOT NULL DEFAULT 0", VERSION_COLUMN, sql_type(&FieldType::Integer, dialect, config)));
+            diesel_columns.push(diesel_column(VERSION_COLUMN, diesel_type(&FieldType::Integer, dialect), true));
+        }
+        definitions.extend(constraints);
+        tables.push(TableContext {
+            name: entity.plural.clone(),
+            definitions,
+            primary_key: vec!["id".to_string()],
+            diesel_columns,
+            diesel_imports,
+            foreign_keys,
+        });
+
+        for join in &entity.joins {
+            let target_key = config.primary_key_of(&join.target.name);
+            join_tables.push(TableContext {
+                name: join.table.clone(),
+                definitions: vec![
+                    format!("{} {} NOT NULL", join.owner_column, key_sql_type(key, dialect)),
+                    format!("{} {} NOT NULL", join.target_column, key_sql_type(target_key, dialect)),
+                    format!("PRIMARY KEY ({}, {})", join.owner_column, join.target_column),
+                    format!("FOREIGN KEY ({}) REFERENCES {}(id) ON DELETE CASCADE", join.owner_column, entity.plural),
+                    format!("FOREIGN KEY ({}) REFERENCES {}(id) ON DELETE CASCADE", join.target_column, join.target.plural),
+                ],
+                primary_key: vec![join.owner_column.clone(), join.target_column.clone()],
+                diesel_columns: vec![
+                    diesel_column(&join.owner_column, key_diesel_type(key, dialect), true),
+                    diesel_column(&join.target_column, key_diesel_type(target_key, dialect), true),
+                ],
+                diesel_imports: Vec::new(),
+                foreign_keys: vec![
+                    ForeignKey {
+                        column: join.owner_column.clone(),
+                        table: entity.plural.clone(),
+                        joinable: false,
+                    },
+                    ForeignKey {
+                        column: join.target_column.clone(),
+                        table: join.target.plural.clone(),
+                        joinable: false,
+                    },
+                ],
+            });
+        }
+    }
+    tables.extend(join_tables);
+    if entities.iter().any(|e| e.audited) {
+        tables.push(audit_table(config));
+    }
+    for table in &mut tables {
+        let mut joined: Vec<String> = Vec::new();
+        for fk in &mut table.foreign_keys {
+            fk.joinable = fk.table != table.name && !joined.contains(&fk.table);
+            joined.push(fk.table.clone());
+        }
+    }
+    tables
+}
+
+/// History of every audited entity: one row per create, update or delete, with
+/// the changed fields as a JSON object in `changes`.
+fn audit_table(config: &ProjectConfig) -> TableContext {
+    let dialect = config.database;
+    TableContext {
+        name: "entity_audit".to_string(),
+        definitions: vec![
+            id_column(dialect, PrimaryKey::I64),
+            format!("entity_type {} NOT NULL", string_type(dialect)),
+            format!("entity_id {} NOT NULL", string_type(dialect)),
+            format!("action {} NOT NULL", string_type(dialect)),
+            format!("modified_by {}", string_type(dialect)),
+            format!("modified_date {} NOT NULL", sql_type(&FieldType::DateTime, dialect, config)),
+            "changes TEXT NOT NULL".to_string(),
+        ],
+        primary_key: vec!["id".to_string()],
+        diesel_columns: vec![
+            diesel_column("id", "BigInt", true),
+            diesel_column("entity_type", "Text", true),
+            diesel_column("entity_id", "Text", true),
+            diesel_column("action", "Text", true),
+            diesel_column("modified_by", "Text", false),
+            diesel_column("modified_date", diesel_type(&FieldType::DateTime, dialect), true),
+            diesel_column("changes", "Text", true),
+        ],
+        diesel_imports: Vec::new(),
+        foreign_keys: Vec::new(),
+    }
+}
+
+/// Orders entities so that every referenced entity is created before the ones
+/// pointing at it. Cycles keep their configured order.
+fn creation_order(entities: &[EntityContext]) -> Vec<&EntityContext> {
+    let mut ordered: Vec<&EntityContext> = Vec::new();
+    let mut pending: Vec<&EntityContext> = entities.iter().collect();
+    while !pending.is_empty() {
+        let ready = pending.iter().position(|entity| {
+            entity.references.iter().all(|r| {
+                r.target.name == entity.name
+                    || ordered.iter().any(|o| o.name == r.target.name)
+                    || !pending.iter().any(|p| p.name == r.target.name)
+            })
+        });
+        ordered.push(pending.remove(ready.unwrap_or(0)));
+    }
+    ordered
+}
+
+/// Sequence ids are assigned by the database; the others are generated by the
+/// application and only need a column of the right type.
+fn id_column(dialect: Database, key: PrimaryKey) -> String {
+    if !key.is_sequence() {
+        return format!("id {} PRIMARY KEY", key_sql_type(key, dialect));
+    }
+    match dialect {
+        Database::Postgres => "id BIGSERIAL PRIMARY KEY",
+        Database::MySQL => "id BIGINT AUTO_INCREMENT PRIMARY KEY",
+        Database::SQLite | Database::MongoDB => "id INTEGER PRIMARY KEY AUTOINCREMENT",
+    }
+    .to_string()
+}
+
+/// Column type of an id, as stored in foreign keys and join tables.
+fn key_sql_type(key: PrimaryKey, dialect: Database) -> &'static str {
+    match (key, dialect) {
+        (PrimaryKey::U64 | PrimaryKey::I64, _) => "BIGINT",
+        (PrimaryKey::UuidV4 | PrimaryKey::UuidV7, Database::Postgres) => "UUID",
+        (PrimaryKey::UuidV4 | PrimaryKey::UuidV7, Database::MySQL) => "CHAR(36)",
+        (PrimaryKey::Ulid, Database::Postgres | Database::MySQL) => "CHAR(26)",
+        (_, _) => "TEXT",
+    }
+}
+
+fn key_diesel_type(key: PrimaryKey, dialect: Database) -> &'static str {
+    match (key, dialect) {
+        (PrimaryKey::U64 | PrimaryKey::I64, _) => "BigInt",
+        (PrimaryKey::UuidV4 | PrimaryKey::UuidV7, Database::Postgres) => "Uuid",
+        _ => "Text",
+    }
+}
+
+fn string_type(dialect: Database) -> &'static str {
+    match dialect {
+        Database::MySQL => "VARCHAR(255)",
+        _ => "TEXT",
+    }
+}
+
+/// Enums use the Postgres type created by the migration, a MySQL `ENUM`, or a
+/// text column that `tables` constrains with a `CHECK` on SQLite. Decimals are
+/// exact `NUMERIC`s; MySQL needs a precision, and 28 digits is what a
+/// `rust_decimal::Decimal` holds.
+fn sql_type(field_type: &FieldType, dialect: Database, config: &ProjectConfig) -> String {
+    if let FieldType::Enum(name) = field_type {
+        return match (dialect, config.enums.iter().find(|e| &e.name == name)) {
+            (Database::Postgres, _) => snake_case(name),
+            (Database::MySQL, Some(def)) => format!("ENUM({})", EnumContext::new(def, config).sql_values),
+            _ => string_type(dialect).to_string(),
+        };
+    }
+    match (field_type, dialect) {
+        (FieldType::String | FieldType::Enum(_), _) => string_type(dialect),
+        (FieldType::Integer, _) => "INTEGER",
+        (FieldType::Long, _) => "BIGINT",
+        (FieldType::Float, Database::MySQL) => "FLOAT",
+        (FieldType::Float, _) => "REAL",
+        (FieldType::Double, Database::Postgres) => "DOUBLE PRECISION",
+        (FieldType::Double, Database::MySQL) => "DOUBLE",
+        (FieldType::Double, _) => "REAL",
+        (FieldType::Decimal, Database::MySQL) => "DECIMAL(28, 10)",
+        (FieldType::Decimal, _) => "NUMERIC",
+        (FieldType::Boolean, _) => "BOOLEAN",
+        (FieldType::Uuid, Database::Postgres) => "UUID",
+        (FieldType::Uuid, Database::MySQL) => "CHAR(36)",
+        (FieldType::Uuid, _) => "TEXT",
+        (FieldType::DateTime, Database::Postgres) => "TIMESTAMPTZ",
+        (FieldType::DateTime, Database::MySQL) => "TIMESTAMP",
+        (FieldType::DateTime, _) => "TEXT",
+    }
+    .to_string()
+}
+
+fn diesel_type(field_type: &FieldType, dialect: Database) -> &'static str {
+    match (field_type, dialect) {
+        (FieldType::String | FieldType::Enum(_), _) => "Text",
+        (FieldType::Integer, _) => "Integer",
+        (FieldType::Long, _) => "BigInt",
+        (FieldType::Float, _) => "Float",
+        (FieldType::Double, _) => "Double",
+        (FieldType::Decimal, _) => "Numeric",
+        (FieldType::Boolean, _) => "Bool",
+        (FieldType::Uuid, Database::Postgres) => "Uuid",
+        (FieldType::Uuid, _) => "Text",
+        (FieldType::DateTime, Database::Postgres) => "Timestamptz",
+        (FieldType::DateTime, Database::SQLite) => "TimestamptzSqlite",
+        (FieldType::DateTime, _) => "Timestamp",
+    }
+}
+
+fn diesel_column(name: &str, sql_type: &str, required: bool) -> DieselColumn {
+    DieselColumn {
+        name: name.to_string(),
+        sql_type: if required {
+            sql_type.to_string()
+        } else {
+            format!("Nullable<{}>", sql_type)
+        },
+    }
+}
@@ -1,21 +1,34 @@
+pub mod blueprint;
 pub mod core_crate;
 pub mod application_crate;
 pub mod infrastructure_crate;
 pub mod api_crate;
 pub mod root_crate;
 pub mod frontend;
+pub mod entities;
+pub mod enums;
+pub mod contract;
+pub mod migrations;
+pub mod naming;
 pub mod common;
+pub mod conflicts;
+pub mod git;
 pub mod utils;
 
 use crate::config::ProjectConfig;
+use crate::manifest::{ProjectManifest, SchemaHistory, MANIFEST_FILE_NAME};
 use anyhow::{Context, Result};
 use std::path::Path;
 use tokio::fs;
-pub use utils::TemplateSource;
+pub use blueprint::Blueprint;
+pub use conflicts::{ConflictError, ConflictPolicy, ConflictResolver, Resolution, WriteSummary};
+pub use utils::{DiskWriter, OutputFile, OutputSink, OutputTree, TemplateSet, TemplateSource, TemplateTree, ZipWriter};
 
 pub struct Generator<'a> {
     config: ProjectConfig,
     template_root: TemplateSource<'a>,
+    blueprints: Vec<Blueprint<'a>>,
+    schema: SchemaHistory,
 }
 
 impl<'a> Generator<'a> {
@@ -23,35 +36,104 @@ impl<'a> Generator<'a> {
         Self {
             config,
             template_root,
+            blueprints: Vec::new(),
+            schema: SchemaHistory::default(),
         }
     }
 
-    pub async fn generate(&self, output_dir: &Path) -> Result<()> {
+    /// Layers `blueprints` over the templates, each over the ones before it.
+    pub fn with_blueprints(mut self, blueprints: Vec<Blueprint<'a>>) -> Self {
+        self.blueprints = blueprints;
+        self
+    }
+
+    /// Continues the migrations recorded in the manifest of the project being
+    /// regenerated, instead of creating the schema from scratch.
+    pub fn with_schema_history(mut self, schema: SchemaHistory) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    /// Renders the project and writes it to `output_dir`, refusing to
+    /// overwrite files that were modified since they were generated.
+    pub async fn generate(&self, output_dir: &Path) -> Result<WriteSummary> {
+        self.generate_with(output_dir, &mut ConflictPolicy::Fail).await
+    }
+
+    /// Like `generate`, letting `resolver` decide what happens to modified files.
+    pub async fn generate_with(&self, output_dir: &Path, resolver: &mut dyn ConflictResolver) -> Result<WriteSummary> {
+        let files = self.render(output_dir).await?;
         fs::create_dir_all(output_dir).await.context("Failed to create output directory")?;
+        DiskWriter::new(resolver).consume(&files)
+    }
+
+    /// Renders the project under `output_dir` and hands the result to `sink`.
+    pub async fn render_into<S: OutputSink>(&self, output_dir: &Path, sink: &mut S) -> Result<S::Output> {
+        let files = self.render(output_dir).await?;
+        sink.consume(&files)
+    }
+
+    /// Renders the project without writing anything, returning every file that
+    /// `generate` would produce under `output_dir`.
+    pub async fn dry_run(&self, output_dir: &Path) -> Result<OutputTree> {
+        self.render(output_dir).await
+    }
+
+    async fn render(&self, output_dir: &Path) -> Result<OutputTree> {
+        self.config.validate().into_result()?;
+
+        let mut out = OutputTree::new();
+        let template_root = if self.blueprints.is_empty() {
+            self.template_root.clone()
+        } else {
+            let overlays = self.blueprints.iter().map(|b| b.templates.clone());
+            TemplateSource::Layered(std::iter::once(self.template_root.clone()).chain(overlays).collect())
+        };
+        // Parse every template once and build the context once; the per-entity
+        // and per-enum steps extend a copy of it.
+        let templates = TemplateSet::load(template_root.tree())?;
+        let context = utils::create_context(&self.config);
 
         // 1. Generate Root Workspace files
-        root_crate::generate(&self.config, self.template_root.clone(), output_dir).await?;
+        root_crate::generate(&self.config, &templates, &context, output_dir, &mut out).await?;
 
         // 2. Generate Core Crate
         let core_crate_output_dir = output_dir.join("core");
-        fs::create_dir_all(&core_crate_output_dir).await?;
-        core_crate::generate(&self.config, self.template_root.clone(), &core_crate_output_dir).await?;
+        core_crate::generate(&templates, &context, &core_crate_output_dir, &mut out).await?;
 
         // 3. Generate Application Crate
         let application_crate_output_dir = output_dir.join("application");
-        fs::create_dir_all(&application_crate_output_dir).await?;
-        application_crate::generate(&self.config, self.template_root.clone(), &application_crate_output_dir).await?;
+        application_crate::generate(&templates, &context, &application_crate_output_dir, &mut out).await?;
 
         // 4. Generate Infrastructure Crate
         let infrastructure_crate_output_dir = output_dir.join("infrastructure");
-        fs::create_dir_all(&infrastructure_crate_output_dir).await?;
-        infrastructure_crate::generate(&self.config, self.template_root.clone(), &infrastructure_crate_output_dir).await?;
+        infrastructure_crate::generate(&templates, &context, &infrastructure_crate_output_dir, &mut out).await?;
 
         // 5. Generate API Crate
         let api_crate_output_dir = output_dir.join("api");
-        fs::create_dir_all(&api_crate_output_dir).await?;
-        api_crate::generate(&self.config, self.template_root.clone(), &api_crate_output_dir).await?;
+        api_crate::generate(&self.config, &templates, &context, &api_crate_output_dir, &mut out).await?;
+
+        // 6. Generate one vertical slice per entity across all crates, and the enums they use
+        entities::generate(&self.config, &templates, &context, output_dir, &mut out).await?;
+        enums::generate(&self.config, &templates, &context, output_dir, &mut out).await?;
+
+        // 7. Generate the DTOs and handler stubs of an imported API contract
+        contract::generate(&self.config, &templates, &context, output_dir, &mut out).await?;
+
+        // 8. Generate the SQL migrations (and Diesel schema) for every table
+        let schema = migrations::generate(&self.config, &self.schema, &templates, &context, output_dir, &mut out).await?;
+
+        // 9. Persist the answers so the project can be regenerated or extended later
+        let mut manifest = ProjectManifest::new(self.config.clone(), template_root.describe());
+        manifest.blueprints = template_root.overlays().iter().map(TemplateSource::describe).collect();
+        manifest.schema = schema;
+        out.insert(output_dir.join(MANIFEST_FILE_NAME), manifest.to_json()?);
+
+        // 10. Run the extra steps of every blueprint
+        for blueprint in &self.blueprints {
+            blueprint.generate(&context, output_dir, &mut out)?;
+        }
 
-        Ok(())
+        Ok(out)
     }
 }
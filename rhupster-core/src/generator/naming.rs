@@ -0,0 +1,112 @@
+//! The one place names change casing or number. Paths, tables, Rust items,
+//! the template filters and the importers all go through these functions, so
+//! `HTTPServer` is `http_server` wherever it ends up.
+
+/// Lowercase words of a name in any casing: `DeliveryRoute`, `delivery-route`,
+/// `deliveryRoute` and `DELIVERY_ROUTE` all give `delivery`, `route`. A run of
+/// capitals is one word, so `HTTPServer` gives `http`, `server`.
+pub(crate) fn name_words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            words.extend((!word.is_empty()).then(|| std::mem::take(&mut word)));
+            continue;
+        }
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i + 1);
+        let starts_word = c.is_uppercase()
+            && (prev.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit())
+                || (prev.is_some_and(char::is_uppercase) && next.is_some_and(|n| n.is_lowercase())));
+        if starts_word && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.extend(c.to_lowercase());
+    }
+    words.extend((!word.is_empty()).then_some(word));
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+/// `DeliveryRoute` -> `delivery_route`
+pub(crate) fn snake_case(name: &str) -> String {
+    name_words(name).join("_")
+}
+
+/// `DeliveryRoute` -> `delivery-route`
+pub(crate) fn kebab_case(name: &str) -> String {
+    name_words(name).join("-")
+}
+
+/// `DeliveryRoute` -> `DELIVERY_ROUTE`
+pub(crate) fn screaming_snake(name: &str) -> String {
+    snake_case(name).to_uppercase()
+}
+
+/// `driver_id` -> `DriverId`
+pub(crate) fn pascal_case(name: &str) -> String {
+    name_words(name).iter().map(|w| capitalize(w)).collect()
+}
+
+/// `driver_id` -> `driverId`
+pub(crate) fn camel_case(name: &str) -> String {
+    let pascal = pascal_case(name);
+    let mut chars = pascal.chars();
+    chars.next().map(|first| first.to_lowercase().chain(chars).collect()).unwrap_or_default()
+}
+
+/// `license_plate` -> `License Plate`
+pub(crate) fn label(name: &str) -> String {
+    name_words(name).iter().map(|w| capitalize(w)).collect::<Vec<_>>().join(" ")
+}
+
+/// English plural of the last word of a name: `delivery_route` -> `delivery_routes`.
+pub(crate) fn pluralize(name: &str) -> String {
+    if name.ends_with('y') && !name.ends_with("ay") && !name.ends_with("ey") && !name.ends_with("oy") {
+        format!("{}ies", &name[..name.len() - 1])
+    } else if ["s", "x", "z", "ch", "sh"].iter().any(|suffix| name.ends_with(suffix)) {
+        format!("{}es", name)
+    } else {
+        format!("{}s", name)
+    }
+}
+
+/// Reverse of `pluralize`: `delivery_routes` -> `delivery_route`, `statuses` -> `status`.
+/// Words that end in `us` or `ss` are already singular, so `status` stays.
+pub(crate) fn singularize(name: &str) -> String {
+    if let Some(stem) = name.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if ["sses", "shes", "ches", "xes", "zes", "uses"].iter().any(|suffix| name.ends_with(suffix)) {
+        name[..name.len() - 2].to_string()
+    } else if name.ends_with('s') && !name.ends_with("ss") && !name.ends_with("us") {
+        name[..name.len() - 1].to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// A snake_case identifier for a contract name: `licensePlate` -> `license_plate`,
+/// `X-Request-Id` -> `x_request_id`, `type` -> `r#type`.
+pub(crate) fn rust_ident(name: &str) -> String {
+    let ident: String = snake_case(name).chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", ident)
+    } else if ["crate", "self", "super"].contains(&ident.as_str()) {
+        format!("{}_", ident)
+    } else if KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{}", ident)
+    } else {
+        ident
+    }
+}
+
+const KEYWORDS: [&str; 35] = [
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "static", "struct",
+    "trait", "true", "type", "unsafe", "use", "where", "while", "yield",
+];
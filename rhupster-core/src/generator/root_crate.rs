@@ -1,54 +1,31 @@
-use super::utils::{render_file_from_template, TemplateSource};
+use super::utils::{OutputTree, TemplateSet};
 use crate::config::{AIAgent, ProjectConfig};
 use anyhow::Result;
 use std::path::Path;
-use tokio::fs;
+use tera::Context as TeraContext;
 
 pub async fn generate(
     config: &ProjectConfig,
-    template_root: TemplateSource<'_>,
+    templates: &TemplateSet,
+    context: &TeraContext,
     output_dir: &Path,
+    out: &mut OutputTree,
 ) -> Result<()> {
+    let mut render = |template: &str, output_path| templates.render_to(Path::new(template), output_path, context, out);
     // Generate workspace Cargo.toml
-    render_file_from_template(
-        config,
-        template_root.clone(),
-        "root_project/Cargo.toml.tera",
-        output_dir.join("Cargo.toml"),
-    )
-    .await?;
+    render("root_project/Cargo.toml.tera", output_dir.join("Cargo.toml"))?;
     // Generate .env.example
-    render_file_from_template(
-        config,
-        template_root.clone(),
-        "root_project/.env.example.tera",
-        output_dir.join(".env.example"),
-    )
-    .await?;
+    render("root_project/.env.example.tera", output_dir.join(".env.example"))?;
     // Generate .gitignore
-    render_file_from_template(
-        config,
-        template_root.clone(),
-        "root_project/.gitignore.tera",
-        output_dir.join(".gitignore"),
-    )
-    .await?;
+    render("root_project/.gitignore.tera", output_dir.join(".gitignore"))?;
     // Generate README.md
-    render_file_from_template(
-        config,
-        template_root.clone(),
-        "common/README.md.tera",
-        output_dir.join("README.md"),
-    )
-    .await?;
+    render("common/README.md.tera", output_dir.join("README.md"))?;
     // Generate STRUCTURE.md
-    render_file_from_template(
-        config,
-        template_root.clone(),
-        "common/STRUCTURE.md.tera",
-        output_dir.join("STRUCTURE.md"),
-    )
-    .await?;
+    render("common/STRUCTURE.md.tera", output_dir.join("STRUCTURE.md"))?;
+    // Generate the entity relationship diagram shown in STRUCTURE.md
+    if !config.entities.is_empty() {
+        render("common/docs/domain.mmd.tera", output_dir.join("docs").join("domain.mmd"))?;
+    }
 
     // Generate AI Agent folders based on selection
     for agent in &config.ai_agents {
@@ -59,12 +36,9 @@ pub async fn generate(
         };
 
         let agent_dir = output_dir.join(folder_name);
-        fs::create_dir_all(&agent_dir).await?;
 
         let template_path = format!("root_project/{}/README.md.tera", folder_name);
-        let output_path = agent_dir.join("README.md");
-        render_file_from_template(config, template_root.clone(), &template_path, output_path)
-            .await?;
+        render(&template_path, agent_dir.join("README.md"))?;
     }
 
     Ok(())
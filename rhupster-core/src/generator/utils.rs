@@ -1,8 +1,18 @@
 use crate::config::ProjectConfig;
-use anyhow::{Context, Result};
-use std::path::{Path, PathBuf};
-use tera::{Tera, Context as TeraContext};
-use tokio::fs;
+use crate::diagram;
+use crate::manifest::GENERATOR_VERSION;
+use super::contract::ContractContext;
+use super::entities::{DomainUses, EntityContext, IdContext};
+use super::naming::{camel_case, kebab_case, pascal_case, pluralize, screaming_snake, singularize, snake_case};
+use super::enums::EnumContext;
+use super::git;
+use super::migrations;
+use super::conflicts::{ConflictResolver, Resolution, WriteSummary};
+use anyhow::{bail, Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Seek, Write};
+use std::path::{Component, Path, PathBuf};
+use tera::{Tera, Context as TeraContext, Value};
 use walkdir::WalkDir;
 use include_dir::Dir;
 
@@ -10,9 +20,20 @@ use include_dir::Dir;
 pub enum TemplateSource<'a> {
     Path(PathBuf),
     Embedded(&'a Dir<'a>),
+    /// A revision of a git repository, checked out at `dir` by `TemplateSource::git`.
+    Git { url: String, rev: String, commit: String, dir: PathBuf },
+    /// Sources stacked on a base one, such as blueprints: a file of a later
+    /// layer replaces the file at the same relative path of an earlier one.
+    Layered(Vec<TemplateSource<'a>>),
 }
 
 impl<'a> TemplateSource<'a> {
+    /// Fetches `rev` of the template repository at `url`, caching it below `cache_dir`.
+    pub fn git(url: &str, rev: &str, cache_dir: &Path) -> Result<Self> {
+        let checkout = git::checkout(url, rev, cache_dir)?;
+        Ok(TemplateSource::Git { url: url.to_string(), rev: rev.to_string(), commit: checkout.commit, dir: checkout.dir })
+    }
+
     pub fn join(&self, path: &str) -> Option<TemplateSource<'a>> {
         match self {
             TemplateSource::Path(p) => Some(TemplateSource::Path(p.join(path))),
@@ -20,237 +41,616 @@ impl<'a> TemplateSource<'a> {
                 let full_path = d.path().join(path);
                 d.get_dir(full_path).map(TemplateSource::Embedded)
             },
+            TemplateSource::Git { url, rev, commit, dir } => Some(TemplateSource::Git {
+                url: url.clone(),
+                rev: rev.clone(),
+                commit: commit.clone(),
+                dir: dir.join(path),
+            }),
+            TemplateSource::Layered(layers) => Some(TemplateSource::Layered(
+                layers.iter().filter_map(|layer| layer.join(path)).filter(|layer| layer.exists()).collect(),
+            )),
         }
     }
 
+    /// The sources layered over the base one, in order.
+    pub fn overlays(&self) -> &[TemplateSource<'a>] {
+        match self {
+            TemplateSource::Layered(layers) => layers.get(1..).unwrap_or_default(),
+            _ => &[],
+        }
+    }
+
+    /// Human readable origin of the templates, recorded in the project manifest.
+    /// Git sources are pinned to the commit they resolved to, so the result can
+    /// be passed back to `--templates`.
+    pub fn describe(&self) -> String {
+        match self {
+            TemplateSource::Path(p) => p.canonicalize().unwrap_or_else(|_| p.clone()).display().to_string(),
+            TemplateSource::Embedded(_) => format!("embedded@{}", GENERATOR_VERSION),
+            TemplateSource::Git { url, commit, .. } => format!("{}{}#{}", git::SPEC_PREFIX, url, commit),
+            TemplateSource::Layered(layers) => layers.first().map(TemplateSource::describe).unwrap_or_default(),
+        }
+    }
+
+    /// The directory behind this source, read the same way for every variant.
+    pub fn tree(&self) -> &dyn TemplateTree {
+        match self {
+            TemplateSource::Path(p) | TemplateSource::Git { dir: p, .. } => p,
+            TemplateSource::Embedded(d) => *d,
+            TemplateSource::Layered(layers) => layers,
+        }
+    }
+
+    /// Every file below this directory with its path relative to it.
+    pub fn read_files(&self) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        self.tree().files()
+    }
+
     pub fn exists(&self) -> bool {
         match self {
-            TemplateSource::Path(p) => p.exists(),
+            TemplateSource::Path(p) | TemplateSource::Git { dir: p, .. } => p.exists(),
             TemplateSource::Embedded(_) => true,
+            TemplateSource::Layered(layers) => layers.iter().any(TemplateSource::exists),
         }
     }
 
     pub fn has_file(&self, path: &str) -> bool {
         match self {
-            TemplateSource::Path(p) => p.join(path).exists(),
+            TemplateSource::Path(p) | TemplateSource::Git { dir: p, .. } => p.join(path).exists(),
             TemplateSource::Embedded(d) => {
                 let full_path = d.path().join(path);
                 d.get_file(full_path).is_some()
             },
+            TemplateSource::Layered(layers) => layers.iter().any(|layer| layer.has_file(path)),
         }
     }
 }
 
-pub fn create_context(config: &ProjectConfig) -> TeraContext {
-    let mut context = TeraContext::new();
-    context.insert("name", &config.name);
-    context.insert("database", &config.database.to_string().to_lowercase());
-    context.insert("orm", &config.orm.to_string().to_lowercase());
-    context.insert("hateoas", &config.hateoas);
-    
-    let infra_strings: Vec<String> = config.infrastructure.iter().map(|i| i.to_string().to_lowercase()).collect();
-    context.insert("infrastructure", &infra_strings);
-    
-    context.insert("frontend", &config.frontend.to_string().to_lowercase());
-    context.insert("router_strategy", &config.router_strategy.to_string());
-    context.insert("api_ui", &config.api_ui.to_string().to_lowercase());
-    
-    match &config.authentication {
-        crate::config::Authentication::Basic => {
-            context.insert("auth_type", "basic");
-            context.insert("authentication", "basic");
-        },
-        crate::config::Authentication::Jwt => {
-            context.insert("auth_type", "jwt");
-            context.insert("authentication", "jwt");
-        },
-        crate::config::Authentication::OAuth2(providers) => {
-            context.insert("auth_type", "oauth2");
-            context.insert("authentication", "oauth2");
-            let provider_strings: Vec<String> = providers.iter().map(|p| p.to_string().to_lowercase()).collect();
-            context.insert("oauth_providers", &provider_strings);
-        },
-        crate::config::Authentication::None => {
-            context.insert("auth_type", "none");
-            context.insert("authentication", "none");
+/// A directory of templates, on disk or embedded in the binary.
+pub trait TemplateTree {
+    /// Every file below the directory with its path relative to it, sorted by path.
+    fn files(&self) -> Result<Vec<(PathBuf, Vec<u8>)>>;
+    /// Contents of one file, by path relative to the directory.
+    fn file(&self, relative: &Path) -> Result<Vec<u8>>;
+    /// Where a file of the directory comes from, recorded with what it renders to.
+    fn locate(&self, relative: &Path) -> PathBuf;
+}
+
+impl TemplateTree for PathBuf {
+    fn files(&self) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        let mut files = Vec::new();
+        for entry in WalkDir::new(self).min_depth(1) {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                let relative = entry.path().strip_prefix(self)?.to_path_buf();
+                files.push((relative, std::fs::read(entry.path())?));
+            }
         }
+        files.sort();
+        Ok(files)
     }
 
-    let mut devops_map = std::collections::HashMap::new();
-    devops_map.insert("docker_compose", config.devops.docker_compose);
-    context.insert("devops", &devops_map);
+    fn file(&self, relative: &Path) -> Result<Vec<u8>> {
+        let path = self.join(relative);
+        std::fs::read(&path).with_context(|| format!("Template not found: {}", path.display()))
+    }
 
-    context
+    fn locate(&self, relative: &Path) -> PathBuf {
+        self.join(relative)
+    }
 }
 
-pub async fn render_dir_recursive(config: &ProjectConfig, src: TemplateSource<'_>, dst: &Path) -> Result<()> {
-    match src {
-        TemplateSource::Path(path) => render_dir_recursive_path(config, &path, dst).await,
-        TemplateSource::Embedded(dir) => render_dir_recursive_embedded(config, dir, dst).await,
+impl TemplateTree for Dir<'_> {
+    fn files(&self) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        let mut files = Vec::new();
+        let mut stack = vec![self];
+        while let Some(dir) = stack.pop() {
+            for file in dir.files() {
+                let relative = file.path().strip_prefix(self.path()).unwrap_or(file.path());
+                files.push((relative.to_path_buf(), file.contents().to_vec()));
+            }
+            stack.extend(dir.dirs());
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    fn file(&self, relative: &Path) -> Result<Vec<u8>> {
+        self.get_file(self.path().join(relative))
+            .map(|file| file.contents().to_vec())
+            .with_context(|| format!("Template not found: {}", relative.display()))
     }
-}
 
-pub async fn render_dir_recursive_filtering(config: &ProjectConfig, src: TemplateSource<'_>, dst: &Path, ignore_dir: &str) -> Result<()> {
-     match src {
-        TemplateSource::Path(path) => render_dir_recursive_path_filtering(config, &path, dst, ignore_dir).await,
-        TemplateSource::Embedded(dir) => render_dir_recursive_embedded_filtering(config, dir, dst, ignore_dir).await,
+    fn locate(&self, relative: &Path) -> PathBuf {
+        self.path().join(relative)
     }
 }
 
+impl TemplateTree for Vec<TemplateSource<'_>> {
+    fn files(&self) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        let mut files = BTreeMap::new();
+        for layer in self {
+            files.extend(layer.tree().files()?);
+        }
+        Ok(files.into_iter().collect())
+    }
 
-async fn render_dir_recursive_path(config: &ProjectConfig, src: &Path, dst: &Path) -> Result<()> {
-    let context = create_context(config);
-
-    for entry in WalkDir::new(src).min_depth(1) {
-        let entry = entry?;
-        let relative_path = entry.path().strip_prefix(src)?;
-        let target_path = dst.join(relative_path);
-
-        if entry.file_type().is_dir() {
-            fs::create_dir_all(target_path).await?;
-        } else {
-            let file_name = entry.file_name().to_string_lossy();
-            if file_name.ends_with(".tera") {
-                let new_file_name = file_name.trim_end_matches(".tera");
-                let final_target_path = target_path.with_file_name(new_file_name);
-                
-                let mut local_tera = Tera::default();
-                local_tera.add_template_file(entry.path(), Some("temp"))?;
-                let rendered = local_tera.render("temp", &context)?;
-                fs::write(final_target_path, rendered).await?;
-            } else {
-                fs::copy(entry.path(), target_path).await?;
-            }
+    fn file(&self, relative: &Path) -> Result<Vec<u8>> {
+        match self.iter().rev().find(|layer| layer.has_file(&relative.to_string_lossy())) {
+            Some(layer) => layer.tree().file(relative),
+            None => bail!("Template not found: {}", relative.display()),
         }
     }
-    Ok(())
+
+    fn locate(&self, relative: &Path) -> PathBuf {
+        self.iter()
+            .rev()
+            .find(|layer| layer.has_file(&relative.to_string_lossy()))
+            .map(|layer| layer.tree().locate(relative))
+            .unwrap_or_else(|| relative.to_path_buf())
+    }
 }
 
-async fn render_dir_recursive_path_filtering(config: &ProjectConfig, src: &Path, dst: &Path, ignore_dir: &str) -> Result<()> {
-    let context = create_context(config);
+/// The files of a template directory with every `.tera` file parsed once, so
+/// each can be rendered any number of times. Other files are copied as they are.
+///
+/// Templates can start with front-matter comments. `when` renders the template
+/// only if a Tera expression holds, and `for` renders it once per item of a
+/// collection, with the item in the context under the given name:
+///
+/// ```text
+/// {# when: authentication == "oauth2" #}
+/// {# for: provider in oauth_providers #}
+/// ```
+///
+/// File and directory names may contain Tera expressions too, such as
+/// `{{ provider }}_client.rs.tera`. A template that renders to nothing but
+/// whitespace is left out.
+pub struct TemplateSet {
+    tera: Tera,
+    files: Vec<TemplateFile>,
+}
 
-    for entry in WalkDir::new(src).min_depth(1) {
-        let entry = entry?;
-        let relative_path = entry.path().strip_prefix(src)?;
-        
-        // Skip ignored directory
-        if relative_path.starts_with(ignore_dir) {
-            continue;
-        }
+/// Templates registered in `tera` next to a file, under its name plus a suffix.
+const WHEN: &str = "#when";
+const FOR: &str = "#for";
+const PATH: &str = "#path";
+
+struct TemplateFile {
+    relative: PathBuf,
+    origin: PathBuf,
+    /// Contents of a file copied verbatim; templates live in `tera` under their relative path.
+    contents: Option<Vec<u8>>,
+    /// Whether the template has a `when` condition, registered as `<name>#when`.
+    conditional: bool,
+    /// Loop variable of a `for` template; the collection is registered as `<name>#for`.
+    each: Option<String>,
+    /// Whether the path contains Tera expressions, registered as `<name>#path`.
+    templated_path: bool,
+}
+
+impl TemplateSet {
+    /// Loads every file below `tree`.
+    pub fn load(tree: &dyn TemplateTree) -> Result<Self> {
+        Self::from_files(tree, tree.files()?)
+    }
 
-        let target_path = dst.join(relative_path);
-
-        if entry.file_type().is_dir() {
-            fs::create_dir_all(target_path).await?;
-        } else {
-            let file_name = entry.file_name().to_string_lossy();
-            if file_name.ends_with(".tera") {
-                let new_file_name = file_name.trim_end_matches(".tera");
-                let final_target_path = target_path.with_file_name(new_file_name);
-                
-                let mut local_tera = Tera::default();
-                local_tera.add_template_file(entry.path(), Some("temp"))?;
-                let rendered = local_tera.render("temp", &context)?;
-                fs::write(final_target_path, rendered).await?;
-            } else {
-                fs::copy(entry.path(), target_path).await?;
+    fn from_files(tree: &dyn TemplateTree, files: Vec<(PathBuf, Vec<u8>)>) -> Result<Self> {
+        let mut tera = Tera::default();
+        register_filters(&mut tera);
+        let mut loaded = Vec::new();
+        for (relative, contents) in files {
+            let origin = tree.locate(&relative);
+            let name = template_name(&relative);
+            let templated_path = name.contains("{{") || name.contains("{%");
+            if templated_path {
+                tera.add_raw_template(&format!("{}{}", name, PATH), &name)
+                    .with_context(|| format!("Invalid expression in template path {}", origin.display()))?;
             }
+            if !is_template(&relative) {
+                loaded.push(TemplateFile { relative, origin, contents: Some(contents), conditional: false, each: None, templated_path });
+                continue;
+            }
+
+            let source = String::from_utf8(contents)
+                .with_context(|| format!("Template {} is not valid UTF-8", origin.display()))?;
+            let (front_matter, body) = FrontMatter::split(&source);
+            tera.add_raw_template(&name, body)
+                .with_context(|| format!("Failed to parse template {}", origin.display()))?;
+            if let Some(condition) = front_matter.when {
+                tera.add_raw_template(&format!("{}{}", name, WHEN), &format!("{{% if {} %}}true{{% endif %}}", condition))
+                    .with_context(|| format!("Invalid `when` condition in template {}", origin.display()))?;
+            }
+            let each = match front_matter.each {
+                Some(each) => {
+                    let (variable, collection) = each
+                        .split_once(" in ")
+                        .map(|(v, c)| (v.trim(), c.trim()))
+                        .filter(|(v, _)| !v.is_empty() && v.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+                        .with_context(|| format!("Invalid `for` in template {}: expected `for: item in collection`", origin.display()))?;
+                    tera.add_raw_template(&format!("{}{}", name, FOR), &format!("{{% set items = {} %}}{{{{ items | json_encode() }}}}", collection))
+                        .with_context(|| format!("Invalid `for` collection in template {}", origin.display()))?;
+                    Some(variable.to_string())
+                }
+                None => None,
+            };
+            loaded.push(TemplateFile { relative, origin, contents: None, conditional: front_matter.when.is_some(), each, templated_path });
         }
+        loaded.sort_by(|a, b| a.relative.cmp(&b.relative));
+        Ok(Self { tera, files: loaded })
+    }
+
+    /// Relative paths of every loaded file, sorted.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|f| f.relative.as_path())
+    }
+
+    /// Relative paths of the files below the directory `dir`, sorted.
+    pub fn paths_in<'s>(&'s self, dir: &'s str) -> impl Iterator<Item = &'s Path> {
+        self.paths().filter(move |path| path.starts_with(dir))
+    }
+
+    /// Whether a file was loaded at `relative`, or below it when it is a directory.
+    pub fn contains(&self, relative: &str) -> bool {
+        self.paths_in(relative).next().is_some()
+    }
+
+    fn file(&self, relative: &Path) -> Result<&TemplateFile> {
+        self.files
+            .binary_search_by(|f| f.relative.as_path().cmp(relative))
+            .map(|index| &self.files[index])
+            .ok()
+            .with_context(|| format!("Template not found: {}", relative.display()))
+    }
+
+    fn render(&self, file: &TemplateFile, suffix: &str, context: &TeraContext) -> Result<String> {
+        self.tera
+            .render(&format!("{}{}", template_name(&file.relative), suffix), context)
+            .with_context(|| format!("Failed to render template {}", file.origin.display()))
+    }
+
+    /// The path of the file at `relative` with the expressions in its name
+    /// rendered; `.tera` suffixes are kept.
+    pub fn path(&self, relative: &Path, context: &TeraContext) -> Result<PathBuf> {
+        let file = self.file(relative)?;
+        if !file.templated_path {
+            return Ok(relative.to_path_buf());
+        }
+        let rendered = PathBuf::from(self.render(file, PATH, context)?);
+        if !rendered.components().all(|c| matches!(c, Component::Normal(_))) {
+            bail!("Template path {} rendered to {}, which leaves its directory", relative.display(), rendered.display());
+        }
+        Ok(rendered)
+    }
+
+    /// One context per item of the file's `for` collection, each with the item
+    /// under the loop variable, or just `context` when the file has no loop.
+    pub fn contexts(&self, relative: &Path, context: &TeraContext) -> Result<Vec<TeraContext>> {
+        let file = self.file(relative)?;
+        let Some(variable) = &file.each else {
+            return Ok(vec![context.clone()]);
+        };
+        let items: serde_json::Value = serde_json::from_str(&self.render(file, FOR, context)?)?;
+        let Some(items) = items.as_array() else {
+            bail!("The `for` collection of template {} is not a list", file.origin.display());
+        };
+        Ok(items
+            .iter()
+            .map(|item| {
+                let mut item_context = context.clone();
+                item_context.insert(variable.as_str(), item);
+                item_context
+            })
+            .collect())
     }
-    Ok(())
-}
 
-// Iterative helper for embedded directories
-async fn process_dir_embedded(
-    start_dir: &Dir<'_>,
-    base_src_path: &Path,
-    dst: &Path,
-    context: &TeraContext,
-    ignore_dir: Option<&str>
-) -> Result<()> {
-    let mut stack = vec![start_dir];
-
-    while let Some(dir) = stack.pop() {
-        // Iterate over files in current directory
-        for file in dir.files() {
-            let relative_path = file.path().strip_prefix(base_src_path).unwrap_or(file.path());
-            
-            if let Some(ignore) = ignore_dir {
-                if relative_path.starts_with(ignore) {
-                    continue;
+    /// Renders the file at `relative` into `out` at `target`, dropping a `.tera`
+    /// suffix from the target name when the file is a template. Templates whose
+    /// `when` condition is false or that render blank add nothing.
+    pub fn render_to(&self, relative: &Path, target: PathBuf, context: &TeraContext, out: &mut OutputTree) -> Result<()> {
+        let file = self.file(relative)?;
+        if file.conditional && self.render(file, WHEN, context)? != "true" {
+            return Ok(());
+        }
+        let contents = match &file.contents {
+            Some(contents) => contents.clone(),
+            None => {
+                let rendered = self.render(file, "", context)?;
+                if rendered.trim().is_empty() {
+                    return Ok(());
                 }
+                rendered.into_bytes()
             }
+        };
+        let target = match (file.contents.is_none(), target.to_str().and_then(|t| t.strip_suffix(".tera"))) {
+            (true, Some(stripped)) => PathBuf::from(stripped),
+            _ => target,
+        };
+        out.insert_file(target, OutputFile { contents, template: Some(file.origin.clone()) });
+        Ok(())
+    }
 
-            let target_path = dst.join(relative_path);
-            
-            // Ensure parent dir exists
-            if let Some(parent) = target_path.parent() {
-                fs::create_dir_all(parent).await?;
+    /// Renders every file below `dst`, at its rendered path and once per item of
+    /// its `for` collection, leaving out those below the `skip` directory.
+    pub fn render_all(&self, dst: &Path, context: &TeraContext, skip: Option<&str>, out: &mut OutputTree) -> Result<()> {
+        self.render_dir("", dst, context, skip, out)
+    }
+
+    /// Renders the files below the directory `dir` like `render_all`, at their
+    /// paths relative to `dir`. `skip` is relative to `dir` too.
+    pub fn render_dir(&self, dir: &str, dst: &Path, context: &TeraContext, skip: Option<&str>, out: &mut OutputTree) -> Result<()> {
+        let skip = skip.map(|skip| Path::new(dir).join(skip));
+        for relative in self.paths_in(dir) {
+            if skip.as_ref().is_some_and(|skip| relative.starts_with(skip)) {
+                continue;
             }
-            
-            let file_name = file.path().file_name().unwrap().to_string_lossy();
-            if file_name.ends_with(".tera") {
-                let new_file_name = file_name.trim_end_matches(".tera");
-                let final_target_path = target_path.with_file_name(new_file_name);
-                
-                let mut local_tera = Tera::default();
-                let content = String::from_utf8_lossy(file.contents());
-                local_tera.add_raw_template("temp", &content)?;
-                let rendered = local_tera.render("temp", context)?;
-                fs::write(final_target_path, rendered).await?;
-            } else {
-                fs::write(target_path, file.contents()).await?;
+            for context in self.contexts(relative, context)? {
+                let path = self.path(relative, &context)?;
+                let target = dst.join(path.strip_prefix(dir).unwrap_or(&path));
+                self.render_to(relative, target, &context, out)?;
             }
         }
+        Ok(())
+    }
+}
+
+fn is_template(relative: &Path) -> bool {
+    relative.extension().is_some_and(|ext| ext == "tera")
+}
+
+/// The `{# key: value #}` lines a template starts with.
+#[derive(Default)]
+struct FrontMatter<'a> {
+    when: Option<&'a str>,
+    each: Option<&'a str>,
+}
+
+impl<'a> FrontMatter<'a> {
+    /// Splits the front-matter lines off `source`, returning them and the rest
+    /// of the template. Other leading comments stay part of the template.
+    fn split(source: &'a str) -> (Self, &'a str) {
+        let mut front_matter = Self::default();
+        let mut body = source;
+        while let Some(rest) = body.strip_prefix("{#") {
+            let Some(end) = rest.find("#}") else {
+                break;
+            };
+            let Some((key, value)) = rest[..end].split_once(':') else {
+                break;
+            };
+            let slot = match key.trim() {
+                "when" => &mut front_matter.when,
+                "for" => &mut front_matter.each,
+                _ => break,
+            };
+            *slot = Some(value.trim());
+            let after = &rest[end + 2..];
+            body = after.strip_prefix('\n').unwrap_or(after);
+        }
+        (front_matter, body)
+    }
+}
+
+/// Registers the name filters every template can use, so crate, table, type
+/// and route names are all derived the same way from the project and entity
+/// names: `{{ name | snake_case }}`, `{{ entity.name | plural | kebab_case }}`.
+fn register_filters(tera: &mut Tera) {
+    type Convert = fn(&str) -> String;
+    let filters: [(&str, Convert); 7] = [
+        ("snake_case", snake_case),
+        ("kebab_case", kebab_case),
+        ("screaming_snake", screaming_snake),
+        ("pascal_case", pascal_case),
+        ("camel_case", camel_case),
+        ("plural", pluralize),
+        ("singular", singularize),
+    ];
+    for (name, convert) in filters {
+        tera.register_filter(name, move |value: &Value, _: &HashMap<String, Value>| {
+            let s = value
+                .as_str()
+                .ok_or_else(|| tera::Error::msg(format!("Filter `{}` expects a string, got {}", name, value)))?;
+            Ok(Value::String(convert(s)))
+        });
+    }
+}
+
+/// Tera names templates with `/` on every platform.
+fn template_name(relative: &Path) -> String {
+    relative.iter().map(|c| c.to_string_lossy()).collect::<Vec<_>>().join("/")
+}
+
+/// One file of an `OutputTree`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputFile {
+    pub contents: Vec<u8>,
+    /// Template the file was rendered or copied from; `None` for files built in
+    /// code, such as the manifest.
+    pub template: Option<PathBuf>,
+}
+
+/// Every file produced by a generation run, keyed by destination path.
+/// Nothing touches the disk until the tree is handed to an `OutputSink`.
+#[derive(Debug, Default)]
+pub struct OutputTree {
+    files: BTreeMap<PathBuf, OutputFile>,
+}
+
+impl OutputTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, path: PathBuf, contents: impl Into<Vec<u8>>) {
+        self.insert_file(path, OutputFile { contents: contents.into(), template: None });
+    }
+
+    pub fn insert_file(&mut self, path: PathBuf, file: OutputFile) {
+        self.files.insert(path, file);
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&[u8]> {
+        self.files.get(path).map(|f| f.contents.as_slice())
+    }
+
+    pub fn file(&self, path: &Path) -> Option<&OutputFile> {
+        self.files.get(path)
+    }
+
+    /// Every path with its contents, sorted by path.
+    pub fn iter(&self) -> impl Iterator<Item = (&PathBuf, &Vec<u8>)> {
+        self.files.iter().map(|(path, f)| (path, &f.contents))
+    }
+
+    /// Every path with its contents and metadata, sorted by path.
+    pub fn files(&self) -> impl Iterator<Item = (&PathBuf, &OutputFile)> {
+        self.files.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Where a rendered `OutputTree` goes: the disk, a dry-run listing, a diff, a zip archive, ...
+pub trait OutputSink {
+    type Output;
+
+    fn consume(&mut self, tree: &OutputTree) -> Result<Self::Output>;
+}
+
+/// Writes every file, asking `resolver` about existing files that differ.
+/// All decisions are made before the first write, so aborting leaves the disk untouched.
+pub struct DiskWriter<'r> {
+    resolver: &'r mut dyn ConflictResolver,
+}
+
+impl<'r> DiskWriter<'r> {
+    pub fn new(resolver: &'r mut dyn ConflictResolver) -> Self {
+        Self { resolver }
+    }
+}
+
+impl OutputSink for DiskWriter<'_> {
+    type Output = WriteSummary;
 
-        // Add subdirectories to stack
-        for subdir in dir.dirs() {
-             let relative_path = subdir.path().strip_prefix(base_src_path).unwrap_or(subdir.path());
-             if let Some(ignore) = ignore_dir {
-                if relative_path.starts_with(ignore) {
-                    continue;
+    fn consume(&mut self, tree: &OutputTree) -> Result<WriteSummary> {
+        let mut summary = WriteSummary::default();
+        let mut to_write = Vec::new();
+
+        for (path, contents) in tree.iter() {
+            match std::fs::read(path) {
+                Ok(existing) if existing == *contents => summary.unchanged.push(path.clone()),
+                Ok(existing) => match self.resolver.resolve(path, &existing, contents)? {
+                    Resolution::Overwrite => {
+                        summary.overwritten.push(path.clone());
+                        to_write.push((path, contents));
+                    }
+                    Resolution::Skip => summary.skipped.push(path.clone()),
+                    Resolution::Abort => bail!("Generation aborted; nothing was written."),
+                },
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    summary.created.push(path.clone());
+                    to_write.push((path, contents));
                 }
+                Err(err) => return Err(err).with_context(|| format!("Failed to read {}", path.display())),
+            }
+        }
+
+        for (path, contents) in to_write {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
             }
-            stack.push(subdir);
+            std::fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
         }
+        Ok(summary)
     }
-    Ok(())
 }
 
-async fn render_dir_recursive_embedded(config: &ProjectConfig, src: &Dir<'_>, dst: &Path) -> Result<()> {
-    let context = create_context(config);
-    process_dir_embedded(src, src.path(), dst, &context, None).await
+/// Packs every file into a zip archive written to `writer`, with paths
+/// relative to `base`, and returns the writer once the archive is complete.
+pub struct ZipWriter<W: Write + Seek> {
+    writer: Option<W>,
+    base: PathBuf,
 }
 
-async fn render_dir_recursive_embedded_filtering(config: &ProjectConfig, src: &Dir<'_>, dst: &Path, ignore_dir: &str) -> Result<()> {
-    let context = create_context(config);
-    process_dir_embedded(src, src.path(), dst, &context, Some(ignore_dir)).await
+impl<W: Write + Seek> ZipWriter<W> {
+    pub fn new(writer: W, base: &Path) -> Self {
+        Self { writer: Some(writer), base: base.to_path_buf() }
+    }
 }
 
+impl<W: Write + Seek> OutputSink for ZipWriter<W> {
+    type Output = W;
+
+    fn consume(&mut self, tree: &OutputTree) -> Result<W> {
+        let writer = self.writer.take().context("The zip archive was already written")?;
+        let mut zip = zip::ZipWriter::new(writer);
+        let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        for (path, contents) in tree.iter() {
+            let relative = path.strip_prefix(&self.base).unwrap_or(path);
+            zip.start_file(template_name(relative), options)
+                .with_context(|| format!("Failed to add {} to the zip archive", relative.display()))?;
+            zip.write_all(contents)?;
+        }
+        Ok(zip.finish()?)
+    }
+}
 
-pub async fn render_file_from_template(config: &ProjectConfig, template_root: TemplateSource<'_>, template_path_str: &str, output_path: PathBuf) -> Result<()> {
-    let context = create_context(config);
-    let mut tera = Tera::default();
+pub fn create_context(config: &ProjectConfig) -> TeraContext {
+    let mut context = TeraContext::new();
+    context.insert("name", &config.name);
+    context.insert("database", &config.database.to_string().to_lowercase());
+    context.insert("orm", &config.orm.to_string().to_lowercase());
+    context.insert("hateoas", &config.hateoas);
+    
+    let infra_strings: Vec<String> = config.infrastructure.iter().map(|i| i.to_string().to_lowercase()).collect();
+    context.insert("infrastructure", &infra_strings);
     
-    match template_root {
-        TemplateSource::Path(root) => {
-             let template_file_path = root.join(template_path_str);
-             tera.add_template_file(&template_file_path, Some(template_path_str))?;
-             let rendered = tera.render(template_path_str, &context)?;
-             fs::write(output_path, rendered).await?;
+    context.insert("frontend", &config.frontend.to_string().to_lowercase());
+    context.insert("router_strategy", &config.router_strategy.to_string());
+    context.insert("api_ui", &config.api_ui.to_string().to_lowercase());
+    
+    match &config.authentication {
+        crate::config::Authentication::Basic => {
+            context.insert("auth_type", "basic");
+            context.insert("authentication", "basic");
+        },
+        crate::config::Authentication::Jwt => {
+            context.insert("auth_type", "jwt");
+            context.insert("authentication", "jwt");
+        },
+        crate::config::Authentication::OAuth2(providers) => {
+            context.insert("auth_type", "oauth2");
+            context.insert("authentication", "oauth2");
+            let provider_strings: Vec<String> = providers.iter().map(|p| p.to_string().to_lowercase()).collect();
+            context.insert("oauth_providers", &provider_strings);
         },
-        TemplateSource::Embedded(root) => {
-             // template_path_str is like "root_project/Cargo.toml.tera"
-             // root is the base embedded dir.
-             // We assume template_path_str is relative to root.
-             
-             let file = root.get_file(template_path_str).context(format!("Template not found: {}", template_path_str))?;
-             let content = String::from_utf8_lossy(file.contents());
-             
-             tera.add_raw_template(template_path_str, &content)?;
-             let rendered = tera.render(template_path_str, &context)?;
-             fs::write(output_path, rendered).await?;
+        crate::config::Authentication::None => {
+            context.insert("auth_type", "none");
+            context.insert("authentication", "none");
         }
     }
-    Ok(())
-}
\ No newline at end of file
+
+    let entities: Vec<EntityContext> = config.entities.iter().map(|e| EntityContext::new(e, config)).collect();
+    context.insert("entities", &entities);
+    context.insert("user_id", &IdContext::new(config.primary_key, config.database));
+    let enums: Vec<EnumContext> = config.enums.iter().map(|e| EnumContext::new(e, config)).collect();
+    context.insert("enums", &enums);
+    context.insert("domain_uses", &DomainUses::of(config));
+    context.insert("contract", &ContractContext::new(config));
+    context.insert("tables", &migrations::tables(config));
+    context.insert("er_diagram", &diagram::mermaid(&config.domain_model(), config.primary_key));
+
+    let mut devops_map = std::collections::HashMap::new();
+    devops_map.insert("docker_compose", config.devops.docker_compose);
+    context.insert("devops", &devops_map);
+
+    context
+}
+
@@ -0,0 +1,629 @@
+//! Importer for JHipster `.jdl` files. It understands the parts of JDL that map
+//! onto a Rhupster project:
+//!
+//! ```text
+//! application {
+//!   config {
+//!     baseName fleet
+//!     prodDatabaseType postgresql
+//!     authenticationType jwt
+//!     clientFramework react
+//!   }
+//!   entities *
+//! }
+//!
+//! enum TruckStatus { AVAILABLE, IN_TRANSIT }
+//!
+//! entity Truck {
+//!   licensePlate String required maxlength(20) pattern(/^[A-Z0-9-]+$/)
+//!   status TruckStatus
+//! }
+//!
+//! relationship ManyToOne {
+//!   Truck{driver(login) required} to User with builtInEntity
+//! }
+//!
+//! dto * with mapstruct
+//! service * with serviceImpl
+//! paginate Truck with pagination
+//! ```
+//!
+//! Field and relationship names are converted from camelCase to snake_case.
+//! Everything else (other options, config keys, blob types, display fields,
+//! `deployment` blocks, ...) is skipped with a warning pointing at its line.
+
+use crate::config::{Authentication, Database, Frontend, Infrastructure, Orm, PartialProjectConfig};
+use crate::dsl::{DslError, Lexer, Span, Token};
+use crate::generator::naming::snake_case;
+use crate::model::{Entity, EnumDef, Field, FieldType, Relationship, RelationshipKind};
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Options that take an entity list and are ignored because Rhupster has no
+/// equivalent for them.
+const UNSUPPORTED_OPTIONS: [&str; 10] = [
+    "search",
+    "skipClient",
+    "skipServer",
+    "filter",
+    "readOnly",
+    "angularSuffix",
+    "clientRootFolder",
+    "microservice",
+    "noFluentMethod",
+    "embedded",
+];
+
+/// The result of importing a JDL file: the answers it provides and everything
+/// that had to be left out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JdlImport {
+    pub config: PartialProjectConfig,
+    pub warnings: Vec<JdlWarning>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JdlWarning {
+    pub span: Span,
+    pub message: String,
+}
+
+impl std::fmt::Display for JdlWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.span.line, self.span.column, self.message)
+    }
+}
+
+/// Imports the contents of a `.jdl` file.
+pub fn import(source: &str) -> Result<JdlImport, DslError> {
+    let tokens = Lexer::new(source).tokenize()?;
+    let mut parser = JdlParser {
+        tokens,
+        pos: 0,
+        import: JdlImport::default(),
+        entities: Vec::new(),
+        enums: Vec::new(),
+        relationships: Vec::new(),
+        application_entities: None,
+        application_except: Vec::new(),
+        seen_application: false,
+    };
+    parser.parse_file()?;
+    Ok(parser.finish())
+}
+
+/// Reads and imports a `.jdl` file; errors are reported as `path:line:column`.
+pub fn import_file(path: &Path) -> Result<JdlImport> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read JDL file {}", path.display()))?;
+    import(&source).map_err(|e| anyhow::anyhow!("{}:{}: {}", path.display(), e.span, e.message))
+}
+
+/// A field whose type is still a JDL name, resolved once every enum is known.
+struct RawField {
+    field: Field,
+    type_name: String,
+    type_span: Span,
+}
+
+struct RawEntity {
+    name: String,
+    span: Span,
+    fields: Vec<RawField>,
+}
+
+struct JdlParser {
+    tokens: Vec<(Token, Span)>,
+    pos: usize,
+    import: JdlImport,
+    entities: Vec<RawEntity>,
+    enums: Vec<EnumDef>,
+    relationships: Vec<(Relationship, Span)>,
+    /// The `entities` list of the application block, unless it is `*`.
+    application_entities: Option<Vec<String>>,
+    application_except: Vec<String>,
+    seen_application: bool,
+}
+
+impl JdlParser {
+    fn peek(&self) -> &(Token, Span) {
+        &self.tokens[self.pos]
+    }
+
+    fn next(&mut self) -> (Token, Span) {
+        let token = self.tokens[self.pos].clone();
+        if token.0 != Token::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek().0 == *token {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: Token) -> Result<Span, DslError> {
+        let (found, span) = self.next();
+        if found == token {
+            Ok(span)
+        } else {
+            Err(DslError::new(span, format!("Expected {}, found {}", token, found)))
+        }
+    }
+
+    fn ident(&mut self, what: &str) -> Result<(String, Span), DslError> {
+        match self.next() {
+            (Token::Ident(name), span) => Ok((name, span)),
+            (found, span) => Err(DslError::new(span, format!("Expected {}, found {}", what, found))),
+        }
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(&self.peek().0, Token::Ident(name) if name == keyword)
+    }
+
+    fn warn(&mut self, span: Span, message: impl Into<String>) {
+        self.import.warnings.push(JdlWarning {
+            span,
+            message: message.into(),
+        });
+    }
+
+    fn parse_file(&mut self) -> Result<(), DslError> {
+        loop {
+            let (token, span) = self.next();
+            match token {
+                Token::Eof => return Ok(()),
+                Token::Symbol('@') => self.annotation(span)?,
+                Token::Ident(keyword) => match keyword.as_str() {
+                    "application" => self.application(span)?,
+                    "entity" => {
+                        let entity = self.entity()?;
+                        self.entities.push(entity);
+                    }
+                    "enum" => self.enum_def()?,
+                    "relationship" => self.relationships()?,
+                    _ => self.option(&keyword, span)?,
+                },
+                found => {
+                    return Err(DslError::new(
+                        span,
+                        format!("Expected 'application', 'entity', 'enum', 'relationship' or an option, found {}", found),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// `@paginate(pagination)`, `@dto(mapstruct)`, ... in front of an entity.
+    fn annotation(&mut self, span: Span) -> Result<(), DslError> {
+        let (name, _) = self.ident("an annotation name")?;
+        if self.eat(&Token::LParen) {
+            self.skip_until(&Token::RParen);
+        }
+        match name.as_str() {
+            "dto" | "service" => {}
+            "paginate" => self.warn(span, "Pagination is not generated; the list endpoints return every row"),
+            other => self.warn(span, format!("Annotation '@{}' is not supported and was ignored", other)),
+        }
+        Ok(())
+    }
+
+    /// `dto * with mapstruct`, `paginate A, B with pagination except C`, ...
+    fn option(&mut self, keyword: &str, span: Span) -> Result<(), DslError> {
+        let known = matches!(keyword, "dto" | "service" | "paginate") || UNSUPPORTED_OPTIONS.contains(&keyword);
+        if !known {
+            self.warn(span, format!("'{}' is not supported and was ignored", keyword));
+            self.skip_construct(span);
+            return Ok(());
+        }
+
+        self.entity_list()?;
+        if self.peek_keyword("with") {
+            self.pos += 1;
+            self.ident(&format!("a value for '{}'", keyword))?;
+        }
+        if self.peek_keyword("except") {
+            self.pos += 1;
+            self.entity_list()?;
+        }
+        match keyword {
+            // Every entity gets DTOs and a service.
+            "dto" | "service" => {}
+            "paginate" => self.warn(span, "Pagination is not generated; the list endpoints return every row"),
+            other => self.warn(span, format!("Option '{}' is not supported and was ignored", other)),
+        }
+        Ok(())
+    }
+
+    /// `*`, `all` or `A, B, C`; `None` stands for every entity.
+    fn entity_list(&mut self) -> Result<Option<Vec<String>>, DslError> {
+        if self.eat(&Token::Symbol('*')) {
+            return Ok(None);
+        }
+        if self.peek_keyword("all") {
+            self.pos += 1;
+            return Ok(None);
+        }
+        let mut names = vec![self.ident("an entity name, 'all' or '*'")?.0];
+        while self.eat(&Token::Comma) {
+            names.push(self.ident("an entity name")?.0);
+        }
+        Ok(Some(names))
+    }
+
+    /// Skips an unsupported construct: the rest of its line, including any
+    /// block opened on it.
+    fn skip_construct(&mut self, start: Span) {
+        while self.peek().0 != Token::Eof && self.peek().1.line == start.line {
+            if self.next().0 == Token::LBrace {
+                self.skip_until(&Token::RBrace);
+            }
+        }
+    }
+
+    /// Skips past the `close` matching an already consumed opening token.
+    fn skip_until(&mut self, close: &Token) {
+        let open = match close {
+            Token::RBrace => Token::LBrace,
+            Token::RParen => Token::LParen,
+            _ => Token::Symbol('['),
+        };
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next().0 {
+                Token::Eof => return,
+                token if token == open => depth += 1,
+                token if token == *close => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    fn application(&mut self, span: Span) -> Result<(), DslError> {
+        self.expect(Token::LBrace)?;
+        if self.seen_application {
+            self.warn(span, "Only the first application block is imported");
+            self.skip_until(&Token::RBrace);
+            return Ok(());
+        }
+        self.seen_application = true;
+
+        loop {
+            let (token, span) = self.next();
+            match token {
+                Token::RBrace => return Ok(()),
+                Token::Ident(keyword) if keyword == "config" => self.application_config()?,
+                Token::Ident(keyword) if keyword == "entities" => {
+                    self.application_entities = self.entity_list()?;
+                    if self.peek_keyword("except") {
+                        self.pos += 1;
+                        self.application_except = self.entity_list()?.unwrap_or_default();
+                    }
+                }
+                Token::Ident(keyword) => self.option(&keyword, span)?,
+                found => return Err(DslError::new(span, format!("Expected 'config', 'entities' or '}}', found {}", found))),
+            }
+        }
+    }
+
+    fn application_config(&mut self) -> Result<(), DslError> {
+        self.expect(Token::LBrace)?;
+        while !self.eat(&Token::RBrace) {
+            let (key, span) = self.ident("a config option or '}'")?;
+            let value = match self.next() {
+                (Token::Ident(mut value), _) => {
+                    // Dotted values such as `packageName com.example.fleet`.
+                    while self.eat(&Token::Symbol('.')) {
+                        value.push('.');
+                        value.push_str(&self.ident("a name after '.'")?.0);
+                    }
+                    value
+                }
+                (Token::Str(value), _) => value,
+                (Token::Int(value), _) => value.to_string(),
+                (Token::Symbol('['), _) => {
+                    self.skip_until(&Token::Symbol(']'));
+                    String::new()
+                }
+                (found, span) => return Err(DslError::new(span, format!("Expected a value for '{}', found {}", key, found))),
+            };
+            self.config_option(&key, &value, span);
+        }
+        Ok(())
+    }
+
+    fn config_option(&mut self, key: &str, value: &str, span: Span) {
+        let config = &mut self.import.config;
+        match (key, value) {
+            ("baseName", name) => config.name = Some(snake_case(name).replace('_', "-")),
+            ("applicationType", "monolith") => {}
+            ("prodDatabaseType" | "databaseType", "postgresql") => config.database = Some(Database::Postgres),
+            ("prodDatabaseType" | "databaseType", "mysql") => config.database = Some(Database::MySQL),
+            ("prodDatabaseType" | "databaseType", "mariadb") => {
+                config.database = Some(Database::MySQL);
+                self.warn(span, "MariaDB is generated as MySQL");
+            }
+            ("prodDatabaseType" | "databaseType", "mongodb") => {
+                config.database = Some(Database::MongoDB);
+                config.orm = Some(Orm::None);
+            }
+            ("databaseType", "sql") => {}
+            ("authenticationType", "jwt") => config.authentication = Some(Authentication::Jwt),
+            ("authenticationType", "oauth2") => {
+                config.authentication = Some(Authentication::OAuth2(Vec::new()));
+                self.warn(span, "JDL does not name OAuth2 providers; add them to the imported config");
+            }
+            ("clientFramework", "react") => config.frontend = Some(Frontend::React),
+            ("clientFramework", "vue") => config.frontend = Some(Frontend::Vue),
+            ("clientFramework", "angular" | "angularX") => config.frontend = Some(Frontend::Angular),
+            ("clientFramework", "svelte") => config.frontend = Some(Frontend::Svelte),
+            ("clientFramework", "no") => config.frontend = Some(Frontend::None),
+            ("cacheProvider", "redis") => add_infrastructure(config, Infrastructure::Redis),
+            ("messageBroker", "kafka") => add_infrastructure(config, Infrastructure::Kafka),
+            (
+                "applicationType" | "prodDatabaseType" | "databaseType" | "authenticationType" | "clientFramework"
+                | "cacheProvider" | "messageBroker",
+                value,
+            ) => self.warn(span, format!("{} '{}' is not supported and was ignored", key, value)),
+            (key, _) => self.warn(span, format!("Config option '{}' is not supported and was ignored", key)),
+        }
+    }
+
+    fn entity(&mut self) -> Result<RawEntity, DslError> {
+        let (name, span) = self.ident("an entity name")?;
+        if self.eat(&Token::LParen) {
+            let (table, table_span) = self.ident("a table name")?;
+            self.expect(Token::RParen)?;
+            self.warn(table_span, format!("Custom table name '{}' was ignored", table));
+        }
+        let mut fields = Vec::new();
+        if self.eat(&Token::LBrace) {
+            while !self.eat(&Token::RBrace) {
+                if self.eat(&Token::Symbol('@')) {
+                    let (annotation, annotation_span) = self.ident("an annotation name")?;
+                    if self.eat(&Token::LParen) {
+                        self.skip_until(&Token::RParen);
+                    }
+                    self.warn(annotation_span, format!("Field annotation '@{}' was ignored", annotation));
+                    continue;
+                }
+                if let Some(field) = self.field()? {
+                    fields.push(field);
+                }
+                self.eat(&Token::Comma);
+            }
+        }
+        Ok(RawEntity { name, span, fields })
+    }
+
+    fn field(&mut self) -> Result<Option<RawField>, DslError> {
+        let (name, name_span) = self.ident("a field name or '}'")?;
+        let (type_name, type_span) = self.ident(&format!("a type for field '{}'", name))?;
+        let mut field = Field::new(snake_case(&name), FieldType::String);
+
+        // Validations stay on the field's line, as in JHipster.
+        while let (Token::Ident(keyword), span) = self.peek().clone() {
+            if span.line != name_span.line {
+                break;
+            }
+            self.pos += 1;
+            match keyword.as_str() {
+                "required" => field.required = true,
+                "unique" => field.unique = true,
+                "min" | "minlength" => field.min = Some(self.int_argument(&keyword)?),
+                "max" | "maxlength" => field.max = Some(self.int_argument(&keyword)?),
+                "pattern" => {
+                    self.expect(Token::LParen)?;
+                    match self.next() {
+                        (Token::Str(pattern), _) | (Token::Regex(pattern), _) => field.pattern = Some(pattern),
+                        (found, span) => {
+                            return Err(DslError::new(span, format!("Expected a pattern like /^[A-Z]+$/, found {}", found)))
+                        }
+                    }
+                    self.expect(Token::RParen)?;
+                }
+                other => {
+                    if self.eat(&Token::LParen) {
+                        self.skip_until(&Token::RParen);
+                    }
+                    self.warn(span, format!("Validation '{}' on field '{}' was ignored", other, name));
+                }
+            }
+        }
+
+        if field.name == "id" {
+            self.warn(name_span, "The 'id' field is generated automatically and was ignored");
+            return Ok(None);
+        }
+        Ok(Some(RawField {
+            field,
+            type_name,
+            type_span,
+        }))
+    }
+
+    fn int_argument(&mut self, validation: &str) -> Result<i64, DslError> {
+        self.expect(Token::LParen)?;
+        let value = match self.next() {
+            (Token::Int(value), _) => value,
+            (found, span) => {
+                return Err(DslError::new(span, format!("Expected a whole number for {}, found {}", validation, found)))
+            }
+        };
+        self.expect(Token::RParen)?;
+        Ok(value)
+    }
+
+    fn enum_def(&mut self) -> Result<(), DslError> {
+        let (name, _) = self.ident("an enum name")?;
+        self.expect(Token::LBrace)?;
+        let mut values = Vec::new();
+        while !self.eat(&Token::RBrace) {
+            let (value, _) = self.ident("an enum value or '}'")?;
+            // `IN_TRANSIT (In transit)`: JHipster's custom value is only a label here.
+            if self.eat(&Token::LParen) {
+                let span = self.peek().1;
+                self.skip_until(&Token::RParen);
+                self.warn(span, format!("Custom value of '{}' was ignored", value));
+            }
+            values.push(value);
+            self.eat(&Token::Comma);
+        }
+        self.enums.push(EnumDef::new(name, values));
+        Ok(())
+    }
+
+    fn relationships(&mut self) -> Result<(), DslError> {
+        let (kind, kind_span) = self.ident("a relationship kind")?;
+        let kind: RelationshipKind = kind
+            .parse()
+            .map_err(|e: anyhow::Error| DslError::new(kind_span, e.to_string()))?;
+        self.expect(Token::LBrace)?;
+
+        while !self.eat(&Token::RBrace) {
+            if self.eat(&Token::Symbol('@')) {
+                let (annotation, span) = self.ident("an annotation name")?;
+                if self.eat(&Token::LParen) {
+                    self.skip_until(&Token::RParen);
+                }
+                self.warn(span, format!("Relationship annotation '@{}' was ignored", annotation));
+                continue;
+            }
+            let (from, from_span) = self.ident("an entity name or '}'")?;
+            let (from_field, required) = self.relationship_field()?;
+            match self.next() {
+                (Token::Ident(keyword), _) if keyword == "to" => {}
+                (found, span) => return Err(DslError::new(span, format!("Expected 'to', found {}", found))),
+            }
+            let (to, _) = self.ident("an entity name")?;
+            let (to_field, _) = self.relationship_field()?;
+            if self.peek_keyword("with") {
+                self.pos += 1;
+                let (with, span) = self.ident("a relationship option")?;
+                if with != "builtInEntity" {
+                    self.warn(span, format!("Relationship option '{}' was ignored", with));
+                }
+            }
+            self.relationships.push((
+                Relationship {
+                    kind,
+                    from,
+                    from_field,
+                    to,
+                    to_field,
+                    required,
+                    fetch: Default::default(),
+                },
+                from_span,
+            ));
+            self.eat(&Token::Comma);
+        }
+        Ok(())
+    }
+
+    /// The optional `{field}`, `{field(display) required}` after an entity in a relationship.
+    fn relationship_field(&mut self) -> Result<(Option<String>, bool), DslError> {
+        if !self.eat(&Token::LBrace) {
+            return Ok((None, false));
+        }
+        let (field, _) = self.ident("a relationship field name")?;
+        if self.eat(&Token::LParen) {
+            let (display, span) = self.ident("a display field")?;
+            self.expect(Token::RParen)?;
+            self.warn(span, format!("Display field '{}' of '{}' was ignored", display, field));
+        }
+        let required = self.peek_keyword("required");
+        if required {
+            self.pos += 1;
+        }
+        self.expect(Token::RBrace)?;
+        Ok((Some(snake_case(&field)), required))
+    }
+
+    /// Resolves field types, drops what cannot be generated and assembles the config.
+    fn finish(mut self) -> JdlImport {
+        let enum_names: Vec<String> = self.enums.iter().map(|e| e.name.clone()).collect();
+        let mut entities = Vec::new();
+        for raw in std::mem::take(&mut self.entities) {
+            let listed = self.application_entities.as_ref().is_none_or(|names| names.contains(&raw.name));
+            if !listed || self.application_except.contains(&raw.name) {
+                self.warn(raw.span, format!("Entity '{}' is not part of the application and was skipped", raw.name));
+                continue;
+            }
+            if raw.name == "User" {
+                self.warn(raw.span, "Entity 'User' was skipped; every project has a built-in user");
+                continue;
+            }
+            let mut entity = Entity::new(raw.name);
+            for raw_field in raw.fields {
+                if let Some(field) = self.resolve_field(raw_field, &enum_names) {
+                    entity.fields.push(field);
+                }
+            }
+            entities.push(entity);
+        }
+
+        let known = |name: &str| name == "User" || entities.iter().any(|e| e.name == name);
+        let mut relationships = Vec::new();
+        for (relationship, span) in std::mem::take(&mut self.relationships) {
+            if !known(&relationship.from) || !known(&relationship.to) {
+                self.warn(
+                    span,
+                    format!("Relationship between '{}' and '{}' refers to a skipped or unknown entity", relationship.from, relationship.to),
+                );
+                continue;
+            }
+            relationships.push(relationship);
+        }
+
+        self.import.config.entities = Some(entities);
+        self.import.config.enums = Some(std::mem::take(&mut self.enums));
+        self.import.config.relationships = Some(relationships);
+        self.import.warnings.sort_by_key(|w| (w.span.line, w.span.column));
+        self.import
+    }
+
+    fn resolve_field(&mut self, raw: RawField, enum_names: &[String]) -> Option<Field> {
+        let field_type = match raw.type_name.as_str() {
+            "String" | "TextBlob" => FieldType::String,
+            "Integer" => FieldType::Integer,
+            "Long" => FieldType::Long,
+            "BigDecimal" => FieldType::Decimal,
+            "Float" => FieldType::Float,
+            "Double" => FieldType::Double,
+            "Boolean" => FieldType::Boolean,
+            "UUID" => FieldType::Uuid,
+            "Instant" | "ZonedDateTime" => FieldType::DateTime,
+            "LocalDate" => {
+                self.warn(raw.type_span, format!("LocalDate field '{}' is stored as a DateTime", raw.field.name));
+                FieldType::DateTime
+            }
+            "Duration" => {
+                self.warn(raw.type_span, format!("Duration field '{}' is stored as a Long", raw.field.name));
+                FieldType::Long
+            }
+            name if enum_names.iter().any(|e| e == name) => FieldType::Enum(name.to_string()),
+            name => {
+                self.warn(
+                    raw.type_span,
+                    format!("Field '{}' has unsupported type '{}' and was skipped", raw.field.name, name),
+                );
+                return None;
+            }
+        };
+        Some(Field {
+            field_type,
+            ..raw.field
+        })
+    }
+}
+
+fn add_infrastructure(config: &mut PartialProjectConfig, infrastructure: Infrastructure) {
+    config.infrastructure.get_or_insert_with(Vec::new).push(infrastructure);
+}
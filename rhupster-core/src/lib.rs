@@ -1,2 +1,11 @@
 pub mod config;
-pub mod generator;
\ No newline at end of file
+pub mod diagram;
+pub mod dsl;
+pub mod generator;
+pub mod jdl;
+pub mod manifest;
+pub mod model;
+pub mod openapi;
+pub mod presets;
+pub mod schema_import;
+pub mod validation;
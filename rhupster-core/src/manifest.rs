@@ -0,0 +1,109 @@
+use crate::config::ProjectConfig;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// File written at the root of every generated project (like JHipster's `.yo-rc.json`).
+pub const MANIFEST_FILE_NAME: &str = ".rhupster.json";
+
+/// Bumped whenever the shape of the saved `ProjectConfig` changes incompatibly.
+pub const CONFIG_SCHEMA_VERSION: u32 = 1;
+
+pub const GENERATOR_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectManifest {
+    pub generator_version: String,
+    pub schema_version: u32,
+    /// Where the templates came from, e.g. `embedded@0.1.0` or a local path.
+    #[serde(default)]
+    pub templates: String,
+    /// Where the blueprints layered over the templates came from, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blueprints: Vec<String>,
+    pub config: ProjectConfig,
+    /// The SQL migrations generated so far and the schema they build.
+    #[serde(default, skip_serializing_if = "SchemaHistory::is_empty")]
+    pub schema: SchemaHistory,
+}
+
+/// Migrations are never rewritten once generated, as they may have been applied:
+/// a later run compares the configured schema with the one recorded here and
+/// adds a migration for the difference.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaHistory {
+    /// Migration names, oldest first, such as `00000000000001_create_tables`.
+    pub migrations: Vec<String>,
+    /// Every table after the last migration, in creation order.
+    pub tables: Vec<TableSchema>,
+    /// Postgres enum types after the last migration.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enum_types: Vec<EnumTypeSchema>,
+}
+
+impl SchemaHistory {
+    pub fn is_empty(&self) -> bool {
+        self.migrations.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableSchema {
+    pub name: String,
+    /// Column and constraint definitions, as in `CREATE TABLE`.
+    pub definitions: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnumTypeSchema {
+    pub name: String,
+    /// Quoted SQL literals, such as `'IN_TRANSIT'`.
+    pub values: Vec<String>,
+}
+
+impl ProjectManifest {
+    pub fn new(config: ProjectConfig, templates: String) -> Self {
+        Self {
+            generator_version: GENERATOR_VERSION.to_string(),
+            schema_version: CONFIG_SCHEMA_VERSION,
+            templates,
+            blueprints: Vec::new(),
+            config,
+            schema: SchemaHistory::default(),
+        }
+    }
+
+    /// Reads the manifest of the project rooted at `project_dir`, if there is one.
+    pub fn load(project_dir: &Path) -> Result<Option<Self>> {
+        let path = project_dir.join(MANIFEST_FILE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let manifest: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid project manifest {}", path.display()))?;
+
+        if manifest.schema_version > CONFIG_SCHEMA_VERSION {
+            bail!(
+                "{} uses config schema v{}, but this rhupster (v{}) only understands up to v{}. Please upgrade rhupster.",
+                path.display(),
+                manifest.schema_version,
+                GENERATOR_VERSION,
+                CONFIG_SCHEMA_VERSION
+            );
+        }
+        Ok(Some(manifest))
+    }
+
+    pub fn save(&self, project_dir: &Path) -> Result<()> {
+        let path = project_dir.join(MANIFEST_FILE_NAME);
+        std::fs::write(&path, self.to_json()?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize project manifest")
+    }
+}
@@ -0,0 +1,461 @@
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// A domain entity rendered as a vertical slice through every generated crate.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Entity {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Vec<Field>,
+    /// Overrides the project's `primary_key` for this entity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_key: Option<PrimaryKey>,
+    /// Adds who/when columns for creation and last change, and records every
+    /// change in the `entity_audit` history table.
+    #[serde(default)]
+    pub audited: bool,
+    /// Makes `delete` set a `deleted_at` timestamp instead of removing the row,
+    /// with admin endpoints to list and restore deleted rows.
+    #[serde(default)]
+    pub soft_delete: bool,
+    /// Adds a `version` column so concurrent updates fail instead of
+    /// overwriting each other, exposed over HTTP as `ETag` / `If-Match`.
+    #[serde(default)]
+    pub versioned: bool,
+}
+
+impl Entity {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            fields: Vec::new(),
+            primary_key: None,
+            audited: false,
+            soft_delete: false,
+            versioned: false,
+        }
+    }
+}
+
+/// Type of an entity's id and where new ids come from.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PrimaryKey {
+    /// Assigned by a database sequence, stored as `BIGINT`.
+    #[default]
+    U64,
+    /// Assigned by a database sequence, stored as `BIGINT`.
+    I64,
+    /// Random UUID generated by the application.
+    UuidV4,
+    /// Time-ordered UUID generated by the application.
+    UuidV7,
+    /// Time-ordered ULID generated by the application, stored as text.
+    Ulid,
+}
+
+impl PrimaryKey {
+    /// Whether the database assigns the id on insert.
+    pub fn is_sequence(self) -> bool {
+        matches!(self, PrimaryKey::U64 | PrimaryKey::I64)
+    }
+}
+
+impl std::fmt::Display for PrimaryKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FromStr for PrimaryKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+            "u64" => Ok(PrimaryKey::U64),
+            "i64" => Ok(PrimaryKey::I64),
+            "uuidv4" | "uuid4" => Ok(PrimaryKey::UuidV4),
+            "uuidv7" | "uuid7" | "uuid" => Ok(PrimaryKey::UuidV7),
+            "ulid" => Ok(PrimaryKey::Ulid),
+            _ => bail!("Unknown primary key '{}': expected one of u64, i64, uuid-v4, uuid-v7, ulid", s),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub field_type: FieldType,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub unique: bool,
+    /// Minimum length for strings, minimum value for numbers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<i64>,
+    /// Maximum length for strings, maximum value for numbers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+}
+
+impl Field {
+    pub fn new(name: impl Into<String>, field_type: FieldType) -> Self {
+        Self {
+            name: name.into(),
+            field_type,
+            required: false,
+            unique: false,
+            min: None,
+            max: None,
+            pattern: None,
+        }
+    }
+}
+
+impl FromStr for Field {
+    type Err = anyhow::Error;
+
+    /// Parses the `name:type` shorthand used on the command line, e.g. `capacity:float`.
+    /// A trailing `!` marks the field as required: `license_plate:string!`.
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, ty) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Invalid field '{}': expected name:type", s))?;
+        let (ty, required) = match ty.strip_suffix('!') {
+            Some(ty) => (ty, true),
+            None => (ty, false),
+        };
+        if name.is_empty() {
+            bail!("Invalid field '{}': missing field name", s);
+        }
+        Ok(Field {
+            required,
+            ..Field::new(name, FieldType::parse_or_enum(ty)?)
+        })
+    }
+}
+
+/// Serialized as the type name, so config files write `field_type = "String"`
+/// or, for an enum, `field_type = "TruckStatus"`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub enum FieldType {
+    String,
+    Integer,
+    Long,
+    Float,
+    Double,
+    Decimal,
+    Boolean,
+    Uuid,
+    DateTime,
+    /// A value of the enum declared under this name.
+    Enum(String),
+}
+
+impl FieldType {
+    /// The built-in scalar types; enums are declared per domain model.
+    pub const ALL: [FieldType; 9] = [
+        FieldType::String,
+        FieldType::Integer,
+        FieldType::Long,
+        FieldType::Float,
+        FieldType::Double,
+        FieldType::Decimal,
+        FieldType::Boolean,
+        FieldType::Uuid,
+        FieldType::DateTime,
+    ];
+}
+
+impl std::fmt::Display for FieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldType::Enum(name) => write!(f, "{}", name),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+impl FieldType {
+    /// Parses a built-in type name, taking any other PascalCase name as a
+    /// reference to a declared enum.
+    pub fn parse_or_enum(s: &str) -> Result<Self> {
+        s.parse().or_else(|err| {
+            if s.starts_with(|c: char| c.is_ascii_uppercase()) && s.chars().all(|c| c.is_ascii_alphanumeric()) {
+                Ok(FieldType::Enum(s.to_string()))
+            } else {
+                Err(err)
+            }
+        })
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            FieldType::Integer | FieldType::Long | FieldType::Float | FieldType::Double | FieldType::Decimal
+        )
+    }
+}
+
+impl FromStr for FieldType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "string" => Ok(FieldType::String),
+            "int" | "integer" => Ok(FieldType::Integer),
+            "long" => Ok(FieldType::Long),
+            "float" => Ok(FieldType::Float),
+            "double" => Ok(FieldType::Double),
+            "decimal" => Ok(FieldType::Decimal),
+            "bool" | "boolean" => Ok(FieldType::Boolean),
+            "uuid" => Ok(FieldType::Uuid),
+            "datetime" => Ok(FieldType::DateTime),
+            _ => bail!(
+                "Unknown field type '{}': expected one of string, int, long, float, double, decimal, bool, uuid, datetime",
+                s
+            ),
+        }
+    }
+}
+
+impl TryFrom<String> for FieldType {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        FieldType::parse_or_enum(&s)
+    }
+}
+
+impl From<FieldType> for String {
+    fn from(field_type: FieldType) -> Self {
+        field_type.to_string()
+    }
+}
+
+/// An enumeration that entity fields can use as their type.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnumDef {
+    pub name: String,
+    pub values: Vec<String>,
+    /// Values stored and serialized differently from their identifier in
+    /// `values`, e.g. `in_repair` -> `in-repair` from an OpenAPI contract.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub wire_values: BTreeMap<String, String>,
+}
+
+impl EnumDef {
+    pub fn new(name: impl Into<String>, values: Vec<String>) -> Self {
+        Self { name: name.into(), values, wire_values: BTreeMap::new() }
+    }
+
+    /// What `value` is stored and serialized as.
+    pub fn wire_value<'a>(&'a self, value: &'a str) -> &'a str {
+        self.wire_values.get(value).map_or(value, String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum RelationshipKind {
+    OneToOne,
+    OneToMany,
+    ManyToOne,
+    ManyToMany,
+}
+
+impl std::fmt::Display for RelationshipKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FromStr for RelationshipKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "onetoone" => Ok(RelationshipKind::OneToOne),
+            "onetomany" => Ok(RelationshipKind::OneToMany),
+            "manytoone" => Ok(RelationshipKind::ManyToOne),
+            "manytomany" => Ok(RelationshipKind::ManyToMany),
+            _ => bail!(
+                "Unknown relationship '{}': expected OneToOne, OneToMany, ManyToOne or ManyToMany",
+                s
+            ),
+        }
+    }
+}
+
+/// Whether reading an entity also loads what a relationship points at.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FetchType {
+    /// Only the related ids are returned.
+    #[default]
+    Lazy,
+    /// The related entities are loaded and nested in the response.
+    Eager,
+}
+
+/// A relationship from one entity to another, e.g. `ManyToOne { Truck{driver} to User }`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Relationship {
+    pub kind: RelationshipKind,
+    pub from: String,
+    /// Name of the relation field on `from`; defaults to the target entity's name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_field: Option<String>,
+    pub to: String,
+    /// Name of the inverse field on `to`, if the relationship is navigable both ways.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_field: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub fetch: FetchType,
+}
+
+impl Relationship {
+    /// The entity that stores the relationship: the one holding the foreign key,
+    /// or the owner of the join table for many-to-many.
+    pub fn owner(&self) -> &str {
+        match self.kind {
+            RelationshipKind::OneToMany => &self.to,
+            _ => &self.from,
+        }
+    }
+
+    /// The entity the owner points at.
+    pub fn target(&self) -> &str {
+        match self.kind {
+            RelationshipKind::OneToMany => &self.from,
+            _ => &self.to,
+        }
+    }
+
+    /// Name of the relation field on the owner, if one was given.
+    pub fn owner_field(&self) -> Option<&str> {
+        match self.kind {
+            RelationshipKind::OneToMany => self.to_field.as_deref(),
+            _ => self.from_field.as_deref(),
+        }
+    }
+}
+
+/// An imported API contract: the schemas and operations of an OpenAPI
+/// document, generated as DTOs and handler stubs that document the same API.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ApiContract {
+    #[serde(default)]
+    pub schemas: Vec<ContractSchema>,
+    #[serde(default)]
+    pub operations: Vec<Operation>,
+}
+
+impl ApiContract {
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty() && self.operations.is_empty()
+    }
+}
+
+/// A component schema, generated as a DTO keeping the contract's property names.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContractSchema {
+    pub name: String,
+    #[serde(default)]
+    pub properties: Vec<ContractProperty>,
+}
+
+/// Types are written like field types, plus the names of other schemas,
+/// `Json` for free-form values and a `[]` suffix for arrays: `Stop[]`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContractProperty {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub schema_type: String,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl std::fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format!("{:?}", self).to_ascii_uppercase())
+    }
+}
+
+/// An operation of the contract, generated as a handler stub.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Operation {
+    /// snake_case name of the handler function.
+    pub name: String,
+    /// The contract's `operationId`, if it had one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    pub method: HttpMethod,
+    /// OpenAPI-style path, e.g. `/trucks/{truckId}/assign`.
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub parameters: Vec<OperationParameter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<String>,
+    #[serde(default)]
+    pub responses: Vec<OperationResponse>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ParameterLocation {
+    Path,
+    Query,
+    Header,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OperationParameter {
+    pub name: String,
+    #[serde(rename = "in")]
+    pub location: ParameterLocation,
+    #[serde(rename = "type")]
+    pub schema_type: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OperationResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// Everything a domain description declares: entities, enums and relationships.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DomainModel {
+    #[serde(default)]
+    pub entities: Vec<Entity>,
+    #[serde(default)]
+    pub enums: Vec<EnumDef>,
+    #[serde(default)]
+    pub relationships: Vec<Relationship>,
+}
@@ -0,0 +1,503 @@
+//! Imports an OpenAPI 3 document (JSON or YAML) as an entity model plus the
+//! API contract it describes.
+//!
+//! Every object in `components.schemas` becomes an entity, except `User`,
+//! which maps to the built-in user:
+//!
+//! - scalar properties become fields, with `required`, `minLength` /
+//!   `maxLength`, `minimum` / `maximum` and `pattern` carried over;
+//! - a `$ref` to another object becomes a many-to-one relationship, an array
+//!   of them a many-to-many one;
+//! - string enums, whether component schemas or inline, become enums.
+//!
+//! The schemas are also kept as they are in the contract, together with every
+//! operation under `paths`, so the generated DTOs and handler stubs document
+//! the same API. Whatever has no equivalent is left out with a warning.
+
+use crate::config::PartialProjectConfig;
+use crate::generator::naming::{pascal_case, rust_ident, singularize};
+use crate::model::{
+    ApiContract, ContractProperty, ContractSchema, Entity, EnumDef, FetchType, Field, FieldType, HttpMethod,
+    Operation, OperationParameter, OperationResponse, ParameterLocation, PrimaryKey, Relationship, RelationshipKind,
+};
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+use std::path::Path;
+
+const METHODS: [(&str, HttpMethod); 5] = [
+    ("get", HttpMethod::Get),
+    ("post", HttpMethod::Post),
+    ("put", HttpMethod::Put),
+    ("patch", HttpMethod::Patch),
+    ("delete", HttpMethod::Delete),
+];
+
+/// The result of importing an OpenAPI document: the entity model and contract
+/// plus everything that had to be left out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OpenApiImport {
+    pub config: PartialProjectConfig,
+    pub warnings: Vec<String>,
+}
+
+/// Imports an OpenAPI 3 document written in JSON or YAML.
+pub fn import(source: &str) -> Result<OpenApiImport> {
+    let document: Value = serde_yaml::from_str(source).context("Failed to parse the OpenAPI document")?;
+    match document.get("openapi").and_then(Value::as_str) {
+        Some(version) if version.starts_with("3.") => {}
+        Some(version) => bail!("OpenAPI {} is not supported: expected a 3.x document", version),
+        None if document.get("swagger").is_some() => {
+            bail!("Swagger 2 documents are not supported: convert the document to OpenAPI 3 first")
+        }
+        None => bail!("Not an OpenAPI document: the 'openapi' version is missing"),
+    }
+
+    let mut importer = Importer {
+        document: &document,
+        entities: Vec::new(),
+        enums: Vec::new(),
+        relationships: Vec::new(),
+        contract: ApiContract::default(),
+        warnings: Vec::new(),
+    };
+    importer.schemas();
+    importer.operations();
+    Ok(importer.finish())
+}
+
+/// Imports a `.json`, `.yaml` or `.yml` OpenAPI document.
+pub fn import_file(path: &Path) -> Result<OpenApiImport> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read OpenAPI document {}", path.display()))?;
+    import(&source).with_context(|| format!("Failed to import {}", path.display()))
+}
+
+/// What a component schema is generated as.
+enum SchemaKind {
+    Object,
+    Enum,
+    /// Anything else, e.g. `type: string` with a format, used through its type.
+    Alias,
+}
+
+struct Importer<'a> {
+    document: &'a Value,
+    entities: Vec<Entity>,
+    enums: Vec<EnumDef>,
+    relationships: Vec<Relationship>,
+    contract: ApiContract,
+    warnings: Vec<String>,
+}
+
+impl<'a> Importer<'a> {
+    fn component_schemas(&self) -> Option<&'a Map<String, Value>> {
+        self.document.pointer("/components/schemas").and_then(Value::as_object)
+    }
+
+    /// Follows a local `$ref`, returning the name it points at and its definition.
+    fn resolve(&self, value: &'a Value) -> (Option<String>, &'a Value) {
+        let mut value = value;
+        let mut name = None;
+        // Bounded, so reference cycles cannot loop forever.
+        for _ in 0..16 {
+            let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+                break;
+            };
+            let Some(target) = reference.strip_prefix('#').and_then(|pointer| self.document.pointer(pointer)) else {
+                break;
+            };
+            name = reference.rsplit('/').next().map(String::from);
+            value = target;
+        }
+        (name, value)
+    }
+
+    fn kind(&self, schema: &Value) -> SchemaKind {
+        let (_, schema) = self.resolve(schema);
+        if schema.get("enum").is_some_and(|values| values.as_array().is_some_and(|v| v.iter().all(Value::is_string))) {
+            SchemaKind::Enum
+        } else if schema.get("type").and_then(Value::as_str) == Some("object")
+            || schema.get("properties").is_some()
+            || schema.get("allOf").is_some()
+        {
+            SchemaKind::Object
+        } else {
+            SchemaKind::Alias
+        }
+    }
+
+    /// Properties and required names of an object, merging `allOf` parts.
+    fn properties(&self, schema: &'a Value) -> (Vec<(&'a String, &'a Value)>, Vec<&'a str>) {
+        let (_, schema) = self.resolve(schema);
+        let mut properties: Vec<(&String, &Value)> = Vec::new();
+        let mut required: Vec<&str> = Vec::new();
+        for part in schema.get("allOf").and_then(Value::as_array).into_iter().flatten() {
+            let (part_properties, part_required) = self.properties(part);
+            properties.extend(part_properties);
+            required.extend(part_required);
+        }
+        if let Some(own) = schema.get("properties").and_then(Value::as_object) {
+            properties.extend(own.iter());
+        }
+        if let Some(own) = schema.get("required").and_then(Value::as_array) {
+            required.extend(own.iter().filter_map(Value::as_str));
+        }
+        (properties, required)
+    }
+
+    fn schemas(&mut self) {
+        let Some(schemas) = self.component_schemas() else {
+            return;
+        };
+        for (name, schema) in schemas {
+            let type_name = type_name(name);
+            match self.kind(schema) {
+                SchemaKind::Enum => self.enum_def(&type_name, schema),
+                SchemaKind::Object => self.object(&type_name, schema),
+                SchemaKind::Alias => {}
+            }
+        }
+    }
+
+    fn enum_def(&mut self, name: &str, schema: &Value) {
+        let mut def = EnumDef::new(name, Vec::new());
+        for value in schema["enum"].as_array().into_iter().flatten().filter_map(Value::as_str) {
+            let identifier: String = value
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+            if identifier != value {
+                // The identifier names the variant; the contract's spelling stays on the wire.
+                def.wire_values.insert(identifier.clone(), value.to_string());
+            }
+            def.values.push(identifier);
+        }
+        self.enums.push(def);
+    }
+
+    fn object(&mut self, name: &str, schema: &'a Value) {
+        let (properties, required) = self.properties(schema);
+        let mut entity = Entity::new(name);
+        let mut contract_schema = ContractSchema { name: name.to_string(), properties: Vec::new() };
+
+        for (property, definition) in properties {
+            let is_required = required.contains(&property.as_str());
+            let mut field_name = rust_ident(property);
+            if field_name.starts_with("r#") || field_name.ends_with('_') {
+                // Keywords cannot name the generated struct fields and columns.
+                let renamed = format!("{}_{}", rust_ident(name), field_name.trim_start_matches("r#").trim_end_matches('_'));
+                self.warnings.push(format!("Property {}.{} was renamed to '{}'", name, property, renamed));
+                field_name = renamed;
+            }
+            let (target, resolved) = self.resolve(definition);
+
+            let schema_type = if resolved.get("enum").is_some() && target.is_none() {
+                // An inline enum gets a type of its own, named after the schema and property.
+                let enum_name = format!("{}{}", name, pascal_case(&field_name));
+                self.enum_def(&enum_name, resolved);
+                enum_name
+            } else {
+                self.contract_type(definition)
+            };
+            contract_schema.properties.push(ContractProperty {
+                name: property.clone(),
+                schema_type: schema_type.clone(),
+                required: is_required,
+            });
+
+            if field_name == "id" {
+                if schema_type == "Uuid" {
+                    entity.primary_key = Some(PrimaryKey::UuidV7);
+                }
+                continue;
+            }
+            let item_target = resolved.get("items").map(|items| self.resolve(items).0);
+            match (&target, self.kind(definition), item_target) {
+                (Some(target), SchemaKind::Object, _) => self.relationships.push(Relationship {
+                    kind: RelationshipKind::ManyToOne,
+                    from: name.to_string(),
+                    from_field: Some(field_name.strip_suffix("_id").unwrap_or(&field_name).to_string()),
+                    to: type_name(target),
+                    to_field: None,
+                    required: is_required,
+                    fetch: FetchType::Lazy,
+                }),
+                (_, _, Some(Some(item)))
+                    if matches!(self.kind(&resolved["items"]), SchemaKind::Object) =>
+                {
+                    self.relationships.push(Relationship {
+                        kind: RelationshipKind::ManyToMany,
+                        from: name.to_string(),
+                        from_field: Some(singularize(&field_name)),
+                        to: type_name(&item),
+                        to_field: None,
+                        required: false,
+                        fetch: FetchType::Lazy,
+                    })
+                }
+                _ => match FieldType::parse_or_enum(&schema_type) {
+                    Ok(field_type) if schema_type != "Json" && !schema_type.ends_with("[]") => {
+                        entity.fields.push(field(field_name, field_type, is_required, resolved));
+                    }
+                    _ => self.warnings.push(format!(
+                        "Property {}.{} has type '{}', which has no field type; it is only kept in the contract",
+                        name, property, schema_type
+                    )),
+                },
+            }
+        }
+
+        self.contract.schemas.push(contract_schema);
+        self.entities.push(entity);
+    }
+
+    /// The contract type of a schema: a field type, a schema or enum name,
+    /// `Json`, or one of those followed by `[]`.
+    fn contract_type(&mut self, schema: &'a Value) -> String {
+        let (target, resolved) = self.resolve(schema);
+        if let Some(target) = target {
+            match self.kind(resolved) {
+                SchemaKind::Object | SchemaKind::Enum => return type_name(&target),
+                SchemaKind::Alias => {}
+            }
+        }
+        let format = resolved.get("format").and_then(Value::as_str);
+        match resolved.get("type").and_then(Value::as_str) {
+            Some("array") => match resolved.get("items") {
+                Some(items) => format!("{}[]", self.contract_type(items)),
+                None => "Json[]".to_string(),
+            },
+            Some("integer") if format == Some("int64") => "Long".to_string(),
+            Some("integer") => "Integer".to_string(),
+            Some("number") if format == Some("float") => "Float".to_string(),
+            Some("number") => "Double".to_string(),
+            Some("string") if format == Some("uuid") => "Uuid".to_string(),
+            Some("string") if format == Some("date-time") => "DateTime".to_string(),
+            Some("string") => "String".to_string(),
+            Some("boolean") => "Boolean".to_string(),
+            Some("object") | None => "Json".to_string(),
+            Some(other) => {
+                self.warnings.push(format!("Type '{}' is not supported and was imported as Json", other));
+                "Json".to_string()
+            }
+        }
+    }
+
+    fn operations(&mut self) {
+        let Some(paths) = self.document.get("paths").and_then(Value::as_object) else {
+            return;
+        };
+        for (path, item) in paths {
+            let (_, item) = self.resolve(item);
+            let Some(item) = item.as_object() else {
+                continue;
+            };
+            for key in item.keys() {
+                if !METHODS.iter().any(|(m, _)| m == key) && !["parameters", "summary", "description", "servers"].contains(&key.as_str()) {
+                    self.warnings.push(format!("{} {} was ignored: the method is not supported", key.to_uppercase(), path));
+                }
+            }
+            let shared: Vec<&Value> = item.get("parameters").and_then(Value::as_array).into_iter().flatten().collect();
+            for (key, method) in METHODS {
+                if let Some(operation) = item.get(key) {
+                    let operation = self.operation(path, method, operation, &shared);
+                    self.contract.operations.push(operation);
+                }
+            }
+        }
+    }
+
+    fn operation(&mut self, path: &str, method: HttpMethod, operation: &'a Value, shared: &[&'a Value]) -> Operation {
+        let endpoint = format!("{} {}", method, path);
+        let operation_id = operation.get("operationId").and_then(Value::as_str).map(String::from);
+        let mut name = match &operation_id {
+            Some(id) => rust_ident(id).trim_start_matches("r#").to_string(),
+            None => handler_name(method, path),
+        };
+        let taken = |name: &str| self.contract.operations.iter().any(|o| o.name == name);
+        if taken(&name) {
+            let base = name.clone();
+            name = (2..).map(|i| format!("{}_{}", base, i)).find(|n| !taken(n)).unwrap_or(base);
+        }
+
+        // Operation parameters override path-level ones with the same name and location.
+        let own: Vec<&Value> = operation.get("parameters").and_then(Value::as_array).into_iter().flatten().collect();
+        let mut parameters: Vec<OperationParameter> = Vec::new();
+        for definition in shared.iter().chain(own.iter()) {
+            let (_, definition) = self.resolve(definition);
+            let Some(parameter_name) = definition.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let location = match definition.get("in").and_then(Value::as_str) {
+                Some("path") => ParameterLocation::Path,
+                Some("query") => ParameterLocation::Query,
+                Some("header") => ParameterLocation::Header,
+                other => {
+                    self.warnings.push(format!(
+                        "Parameter '{}' of {} was ignored: parameters in '{}' are not supported",
+                        parameter_name,
+                        endpoint,
+                        other.unwrap_or("?")
+                    ));
+                    continue;
+                }
+            };
+            let schema_type = match definition.get("schema") {
+                Some(schema) => self.contract_type(schema),
+                None => "String".to_string(),
+            };
+            let parameter = OperationParameter {
+                name: parameter_name.to_string(),
+                location,
+                schema_type,
+                required: location == ParameterLocation::Path
+                    || definition.get("required").and_then(Value::as_bool).unwrap_or(false),
+                description: definition.get("description").and_then(Value::as_str).unwrap_or_default().to_string(),
+            };
+            parameters.retain(|p| p.name != parameter.name || p.location != parameter.location);
+            parameters.push(parameter);
+        }
+
+        let request_body = operation.get("requestBody").and_then(|body| {
+            let (_, body) = self.resolve(body);
+            let body = self.json_body(body, &endpoint, "request body");
+            body.map(|schema| self.contract_type(schema))
+        });
+
+        let mut responses = Vec::new();
+        for (status, response) in operation.get("responses").and_then(Value::as_object).into_iter().flatten() {
+            let Ok(status) = status.parse::<u16>() else {
+                self.warnings.push(format!("The '{}' response of {} was ignored", status, endpoint));
+                continue;
+            };
+            let (_, response) = self.resolve(response);
+            let body = self.json_body(response, &endpoint, &format!("{} response", status));
+            responses.push(OperationResponse {
+                status,
+                description: response.get("description").and_then(Value::as_str).unwrap_or_default().to_string(),
+                body: body.map(|schema| self.contract_type(schema)),
+            });
+        }
+
+        Operation {
+            name,
+            operation_id,
+            method,
+            path: path.to_string(),
+            tag: operation
+                .get("tags")
+                .and_then(Value::as_array)
+                .and_then(|tags| tags.first())
+                .and_then(Value::as_str)
+                .map(String::from),
+            summary: operation
+                .get("summary")
+                .or_else(|| operation.get("description"))
+                .and_then(Value::as_str)
+                .map(|s| s.trim().to_string()),
+            parameters,
+            request_body,
+            responses,
+        }
+    }
+
+    /// The schema of the JSON content of a request body or response, if it has one.
+    fn json_body(&mut self, body: &'a Value, endpoint: &str, what: &str) -> Option<&'a Value> {
+        let content = body.get("content").and_then(Value::as_object)?;
+        let json = content.iter().find(|(media_type, _)| media_type.contains("json"));
+        match json {
+            Some((_, media)) => media.get("schema"),
+            None => {
+                if let Some(media_type) = content.keys().next() {
+                    self.warnings.push(format!(
+                        "The {} of {} is '{}', not JSON, and was left out",
+                        what, endpoint, media_type
+                    ));
+                }
+                None
+            }
+        }
+    }
+
+    fn finish(mut self) -> OpenApiImport {
+        if let Some(index) = self.entities.iter().position(|e| e.name == "User") {
+            self.entities.remove(index);
+            self.relationships.retain(|r| r.from != "User");
+            self.warnings
+                .push("Schema 'User' maps to the built-in User; its properties are only kept in the contract".to_string());
+        }
+
+        // Entities need at least one field of their own.
+        let empty: Vec<String> =
+            self.entities.iter().filter(|e| e.fields.is_empty()).map(|e| e.name.clone()).collect();
+        for name in &empty {
+            self.warnings.push(format!("Schema '{}' has no scalar properties and was not imported as an entity", name));
+        }
+        self.entities.retain(|e| !e.fields.is_empty());
+        let known = |name: &str| name == "User" || self.entities.iter().any(|e| e.name == name);
+        let (relationships, dropped): (Vec<Relationship>, Vec<Relationship>) =
+            self.relationships.into_iter().partition(|r| known(&r.from) && known(&r.to));
+        for relationship in dropped.iter().filter(|r| known(&r.from)) {
+            self.warnings.push(format!(
+                "Relationship {}.{} was ignored: '{}' is not an entity",
+                relationship.from,
+                relationship.from_field.as_deref().unwrap_or_default(),
+                relationship.to
+            ));
+        }
+
+        let title = self.document.pointer("/info/title").and_then(Value::as_str).map(project_name);
+        let config = PartialProjectConfig {
+            name: title.filter(|t| !t.is_empty()),
+            entities: Some(self.entities),
+            enums: Some(self.enums),
+            relationships: Some(relationships),
+            contract: Some(self.contract),
+            ..Default::default()
+        };
+        OpenApiImport { config, warnings: self.warnings }
+    }
+}
+
+fn field(name: String, field_type: FieldType, required: bool, schema: &Value) -> Field {
+    let bound = |keys: [&str; 2]| keys.iter().find_map(|key| schema.get(*key).and_then(Value::as_f64)).map(|v| v as i64);
+    Field {
+        required,
+        min: bound(["minLength", "minimum"]),
+        max: bound(["maxLength", "maximum"]),
+        pattern: schema.get("pattern").and_then(Value::as_str).map(String::from),
+        ..Field::new(name, field_type)
+    }
+}
+
+/// `truck-location` or `truck_location` -> `TruckLocation`; PascalCase names are kept.
+fn type_name(name: &str) -> String {
+    if name.chars().all(|c| c.is_ascii_alphanumeric()) && name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        name.to_string()
+    } else {
+        pascal_case(rust_ident(name).trim_start_matches("r#"))
+    }
+}
+
+/// `GET /trucks/{truckId}/stops` -> `get_trucks_by_truck_id_stops`
+fn handler_name(method: HttpMethod, path: &str) -> String {
+    let mut name = method.to_string().to_lowercase();
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        name.push('_');
+        match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(parameter) => name.push_str(&format!("by_{}", rust_ident(parameter).trim_start_matches("r#"))),
+            None => name.push_str(rust_ident(segment).trim_start_matches("r#")),
+        }
+    }
+    name
+}
+
+/// `Fleet Management API` -> `fleet-management-api`
+fn project_name(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
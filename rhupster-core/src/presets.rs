@@ -0,0 +1,93 @@
+use crate::config::{
+    ApiUi, Authentication, Database, DevOps, Frontend, Infrastructure, Orm, PartialProjectConfig,
+};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Named sets of answers that `rhupster new --preset <name>` starts from.
+/// Anything a preset leaves out is asked by the wizard (or taken from flags).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Presets {
+    #[serde(default)]
+    presets: BTreeMap<String, PartialProjectConfig>,
+}
+
+impl Presets {
+    /// The presets shipped with Rhupster.
+    pub fn builtin() -> Self {
+        let mut presets = BTreeMap::new();
+        presets.insert(
+            "minimal".to_string(),
+            PartialProjectConfig {
+                database: Some(Database::SQLite),
+                orm: Some(Orm::Sqlx),
+                infrastructure: Some(Vec::new()),
+                frontend: Some(Frontend::None),
+                authentication: Some(Authentication::None),
+                ..PartialProjectConfig::default()
+            },
+        );
+        presets.insert(
+            "monolith".to_string(),
+            PartialProjectConfig {
+                database: Some(Database::Postgres),
+                orm: Some(Orm::Sqlx),
+                frontend: Some(Frontend::React),
+                authentication: Some(Authentication::Jwt),
+                api_ui: Some(ApiUi::Swagger),
+                devops: Some(DevOps { docker_compose: true }),
+                ..PartialProjectConfig::default()
+            },
+        );
+        presets.insert(
+            "event-driven".to_string(),
+            PartialProjectConfig {
+                database: Some(Database::Postgres),
+                infrastructure: Some(vec![Infrastructure::Kafka, Infrastructure::Redis]),
+                frontend: Some(Frontend::None),
+                authentication: Some(Authentication::Jwt),
+                ..PartialProjectConfig::default()
+            },
+        );
+        Self { presets }
+    }
+
+    /// Reads user presets from a TOML file with one `[presets.<name>]` table per preset.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read presets file {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Invalid presets file {}", path.display()))
+    }
+
+    /// The built-in presets plus the ones in `path`, if that file exists.
+    /// A user preset with the same name as a built-in one replaces it.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let builtin = Self::builtin();
+        match path {
+            Some(path) if path.exists() => Ok(builtin.merge(Self::from_file(path)?)),
+            _ => Ok(builtin),
+        }
+    }
+
+    /// Adds every preset of `overrides`, replacing presets with the same name.
+    pub fn merge(mut self, overrides: Presets) -> Self {
+        self.presets.extend(overrides.presets);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Result<&PartialProjectConfig> {
+        self.presets.get(name).ok_or_else(|| {
+            anyhow!(
+                "Unknown preset '{}'. Available presets: {}",
+                name,
+                self.names().collect::<Vec<_>>().join(", ")
+            )
+        })
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.presets.keys().map(String::as_str)
+    }
+}
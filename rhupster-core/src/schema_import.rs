@@ -0,0 +1,502 @@
+//! Reverse-engineers an entity model from existing SQL DDL: a `.sql` dump in
+//! the Postgres, MySQL or SQLite dialect, or a SQLite database file.
+//!
+//! Every `CREATE TABLE` becomes an entity named after the singular of the
+//! table (`delivery_routes` -> `DeliveryRoute`), except:
+//!
+//! - `users` / `user`, which maps to the built-in user;
+//! - tables made of exactly two foreign keys, which become many-to-many
+//!   relationships;
+//! - migration bookkeeping tables, which are skipped.
+//!
+//! Single-column foreign keys (inline, as table constraints or added by
+//! `ALTER TABLE`) become many-to-one relationships, or one-to-one when the
+//! column is unique. `ENUM(...)` columns, Postgres `CREATE TYPE ... AS ENUM`
+//! and SQLite `CHECK (col IN (...))` constraints become enums. Columns whose
+//! type has no Rhupster equivalent are skipped with a warning.
+
+use crate::config::{Database, PartialProjectConfig};
+use crate::generator::naming::{pascal_case, singularize, snake_case};
+use crate::model::{Entity, EnumDef, FetchType, Field, FieldType, PrimaryKey, Relationship, RelationshipKind};
+use anyhow::{Context, Result};
+use sqlparser::ast::{
+    AlterTableOperation, ColumnDef, ColumnOption, DataType, Expr, ObjectName, Statement, TableConstraint,
+    UserDefinedTypeRepresentation, Value,
+};
+use sqlparser::dialect::{Dialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect};
+use sqlparser::parser::Parser;
+use std::path::Path;
+
+/// First bytes of every SQLite database file.
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+/// Tables kept by migration tools, never part of the domain.
+const BOOKKEEPING_TABLES: [&str; 7] = [
+    "_sqlx_migrations",
+    "__diesel_schema_migrations",
+    "seaql_migrations",
+    "sqlite_sequence",
+    "flyway_schema_history",
+    "databasechangelog",
+    "databasechangeloglock",
+];
+
+/// The result of importing a schema: the entity model (and the database it
+/// came from) plus everything that had to be left out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaImport {
+    pub config: PartialProjectConfig,
+    pub warnings: Vec<String>,
+}
+
+/// Imports SQL DDL written for `dialect` (Postgres, MySQL or SQLite).
+pub fn import(sql: &str, dialect: Database) -> Result<SchemaImport> {
+    let parser_dialect: Box<dyn Dialect> = match dialect {
+        Database::Postgres => Box::new(PostgreSqlDialect {}),
+        Database::MySQL => Box::new(MySqlDialect {}),
+        Database::SQLite => Box::new(SQLiteDialect {}),
+        Database::MongoDB => anyhow::bail!("MongoDB has no SQL schema to import"),
+    };
+    let statements = Parser::parse_sql(parser_dialect.as_ref(), sql)?;
+
+    let mut schema = Schema::default();
+    for statement in statements {
+        schema.statement(statement);
+    }
+    let mut import = schema.into_import();
+    import.config.database = Some(dialect);
+    Ok(import)
+}
+
+/// Imports a `.sql` file, or the schema of a SQLite database file. `dialect`
+/// defaults to Postgres for `.sql` files and is ignored for databases.
+pub fn import_file(path: &Path, dialect: Option<Database>) -> Result<SchemaImport> {
+    let bytes = std::fs::read(path).with_context(|| format!("Failed to read schema file {}", path.display()))?;
+    let result = if bytes.starts_with(SQLITE_HEADER) {
+        import(&sqlite_schema(path)?, Database::SQLite)
+    } else {
+        let sql = String::from_utf8(bytes).with_context(|| format!("{} is not a text file", path.display()))?;
+        import(&sql, dialect.unwrap_or(Database::Postgres))
+    };
+    result.with_context(|| format!("Failed to import the schema in {}", path.display()))
+}
+
+/// The `CREATE` statements SQLite keeps for the tables and indexes of a database.
+fn sqlite_schema(path: &Path) -> Result<String> {
+    let connection = rusqlite::Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open SQLite database {}", path.display()))?;
+    let mut query = connection
+        .prepare("SELECT sql FROM sqlite_master WHERE sql IS NOT NULL AND name NOT LIKE 'sqlite_%' ORDER BY rowid")?;
+    let statements = query
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(statements.join(";\n"))
+}
+
+#[derive(Debug, Default)]
+struct Table {
+    name: String,
+    columns: Vec<Column>,
+    primary_key: Vec<String>,
+    /// Columns and the table they reference.
+    foreign_keys: Vec<(Vec<String>, String)>,
+    unique: Vec<Vec<String>>,
+}
+
+#[derive(Debug)]
+struct Column {
+    name: String,
+    data_type: DataType,
+    not_null: bool,
+    /// Values of a `CHECK (column IN (...))` constraint.
+    check_values: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default)]
+struct Schema {
+    tables: Vec<Table>,
+    /// Postgres `CREATE TYPE name AS ENUM (...)` types.
+    enum_types: Vec<(String, Vec<String>)>,
+    warnings: Vec<String>,
+}
+
+impl Schema {
+    fn statement(&mut self, statement: Statement) {
+        match statement {
+            Statement::CreateTable(create) => {
+                let mut table = Table {
+                    name: object_name(&create.name),
+                    ..Table::default()
+                };
+                for column in create.columns {
+                    table.add_column(column);
+                }
+                for constraint in create.constraints {
+                    table.add_constraint(constraint);
+                }
+                self.tables.push(table);
+            }
+            Statement::AlterTable { name, operations, .. } => {
+                let name = object_name(&name);
+                let Some(table) = self.tables.iter_mut().find(|t| t.name == name) else {
+                    self.warnings.push(format!("ALTER TABLE on unknown table '{}' was ignored", name));
+                    return;
+                };
+                for operation in operations {
+                    match operation {
+                        AlterTableOperation::AddConstraint(constraint) => table.add_constraint(constraint),
+                        AlterTableOperation::AddColumn { column_def, .. } => table.add_column(column_def),
+                        other => self.warnings.push(format!("'ALTER TABLE {} {}' was ignored", name, other)),
+                    }
+                }
+            }
+            Statement::CreateIndex(index) if index.unique && index.columns.len() == 1 => {
+                let name = object_name(&index.table_name);
+                if let (Some(table), Expr::Identifier(column)) =
+                    (self.tables.iter_mut().find(|t| t.name == name), &index.columns[0].expr)
+                {
+                    table.unique.push(vec![column.value.clone()]);
+                }
+            }
+            Statement::CreateType {
+                name,
+                representation: UserDefinedTypeRepresentation::Enum { labels },
+            } => self
+                .enum_types
+                .push((object_name(&name), labels.into_iter().map(|l| l.value).collect())),
+            // Data, indexes and session settings carry no model information.
+            Statement::Insert(_)
+            | Statement::CreateIndex(_)
+            | Statement::SetVariable { .. }
+            | Statement::StartTransaction { .. }
+            | Statement::Commit { .. }
+            | Statement::Drop { .. } => {}
+            other => {
+                let text = other.to_string();
+                let head: String = text.chars().take(40).collect();
+                self.warnings.push(format!("Statement '{}...' was ignored", head));
+            }
+        }
+    }
+
+    fn into_import(mut self) -> SchemaImport {
+        let tables = std::mem::take(&mut self.tables);
+        let tables: Vec<&Table> = tables
+            .iter()
+            .filter(|t| !BOOKKEEPING_TABLES.contains(&t.name.as_str()))
+            .collect();
+
+        let is_join = |t: &Table| {
+            t.foreign_keys.len() == 2
+                && t.foreign_keys.iter().all(|(columns, _)| columns.len() == 1)
+                && t.columns.len() == 2
+        };
+        let entity_name = |table: &str| -> Option<String> {
+            if is_user_table(table) {
+                return Some("User".to_string());
+            }
+            tables
+                .iter()
+                .find(|t| t.name == table && !is_join(t))
+                .map(|t| pascal_case(&singularize(&field_name(&t.name))))
+        };
+
+        let mut entities = Vec::new();
+        let mut enums = Vec::new();
+        let mut relationships = Vec::new();
+        for table in &tables {
+            if is_user_table(&table.name) {
+                self.warnings.push(format!(
+                    "Table '{}' maps to the built-in User; its columns were ignored",
+                    table.name
+                ));
+                continue;
+            }
+            if is_join(table) {
+                match self.join_relationship(table, &entity_name) {
+                    Some(relationship) => relationships.push(relationship),
+                    None => self.warnings.push(format!(
+                        "Join table '{}' references an unknown table and was skipped",
+                        table.name
+                    )),
+                }
+                continue;
+            }
+
+            let mut entity = Entity::new(entity_name(&table.name).expect("table is an entity"));
+            match table.primary_key.as_slice() {
+                [id] if id == "id" => {}
+                [] => self.warnings.push(format!("Table '{}' has no primary key; entities get a generated id", table.name)),
+                [key] => self.warnings.push(format!(
+                    "Primary key '{}' of '{}' is kept as a unique field next to the generated id",
+                    key, table.name
+                )),
+                keys => self.warnings.push(format!(
+                    "Composite primary key ({}) of '{}' was replaced by a generated id",
+                    keys.join(", "),
+                    table.name
+                )),
+            }
+
+            for column in &table.columns {
+                let primary = table.primary_key.len() == 1 && table.primary_key[0] == column.name;
+                if primary && column.name == "id" {
+                    if column.data_type.to_string().eq_ignore_ascii_case("uuid") {
+                        entity.primary_key = Some(PrimaryKey::UuidV7);
+                    }
+                    continue;
+                }
+                let unique = primary || table.unique.iter().any(|u| u.len() == 1 && u[0] == column.name);
+                if let Some((_, target)) = table
+                    .foreign_keys
+                    .iter()
+                    .find(|(columns, _)| columns.len() == 1 && columns[0] == column.name)
+                {
+                    let Some(to) = entity_name(target) else {
+                        self.warnings.push(format!(
+                            "Column '{}.{}' references unknown table '{}' and was skipped",
+                            table.name, column.name, target
+                        ));
+                        continue;
+                    };
+                    let name = field_name(&column.name);
+                    let from_field = match name.strip_suffix("_id") {
+                        Some(stripped) => stripped.to_string(),
+                        None => {
+                            self.warnings.push(format!(
+                                "Foreign key '{}.{}' is generated as '{}_id'",
+                                table.name, column.name, name
+                            ));
+                            name
+                        }
+                    };
+                    relationships.push(Relationship {
+                        kind: if unique { RelationshipKind::OneToOne } else { RelationshipKind::ManyToOne },
+                        from: entity.name.clone(),
+                        from_field: Some(from_field),
+                        to,
+                        to_field: None,
+                        required: column.not_null,
+                        fetch: FetchType::Lazy,
+                    });
+                    continue;
+                }
+
+                let name = field_name(&column.name);
+                let Some((field_type, max)) = self.field_type(&entity.name, &name, column, &mut enums) else {
+                    self.warnings.push(format!(
+                        "Column '{}.{}' has unsupported type {} and was skipped",
+                        table.name, column.name, column.data_type
+                    ));
+                    continue;
+                };
+                let mut field = Field::new(name, field_type);
+                field.required = column.not_null;
+                field.unique = unique;
+                field.max = max;
+                entity.fields.push(field);
+            }
+            for (columns, _) in table.foreign_keys.iter().filter(|(columns, _)| columns.len() > 1) {
+                self.warnings.push(format!(
+                    "Composite foreign key ({}) of '{}' was ignored",
+                    columns.join(", "),
+                    table.name
+                ));
+            }
+            entities.push(entity);
+        }
+
+        SchemaImport {
+            config: PartialProjectConfig {
+                entities: Some(entities),
+                enums: Some(enums),
+                relationships: Some(relationships),
+                ..PartialProjectConfig::default()
+            },
+            warnings: self.warnings,
+        }
+    }
+
+    /// `delivery_route_trucks (delivery_route_id, truck_id)` ->
+    /// `DeliveryRoute{truck} to Truck`. The owner is the table the join
+    /// table's name starts with, or else its first foreign key.
+    fn join_relationship(&self, table: &Table, entity_name: &dyn Fn(&str) -> Option<String>) -> Option<Relationship> {
+        let mut keys: Vec<&(Vec<String>, String)> = table.foreign_keys.iter().collect();
+        let owns = |(_, target): &&(Vec<String>, String)| table.name.starts_with(&singularize(target));
+        if !owns(&keys[0]) && owns(&keys[1]) {
+            keys.swap(0, 1);
+        }
+        let target_column = field_name(&keys[1].0[0]);
+        Some(Relationship {
+            kind: RelationshipKind::ManyToMany,
+            from: entity_name(&keys[0].1)?,
+            from_field: Some(target_column.strip_suffix("_id").unwrap_or(&target_column).to_string()),
+            to: entity_name(&keys[1].1)?,
+            to_field: None,
+            required: false,
+            fetch: FetchType::Lazy,
+        })
+    }
+
+    /// The field type of a column plus, for strings, its maximum length.
+    /// Enum columns add their enum to `enums`.
+    fn field_type(
+        &mut self,
+        entity: &str,
+        field: &str,
+        column: &Column,
+        enums: &mut Vec<EnumDef>,
+    ) -> Option<(FieldType, Option<i64>)> {
+        // Columns listing the same values share one enum, named after the first of them.
+        let mut declare = |name: String, values: &[String]| {
+            if !values.iter().all(|v| is_enum_value(v)) {
+                return None;
+            }
+            if let Some(existing) = enums.iter().find(|e| e.name == name || e.values == values) {
+                return Some(FieldType::Enum(existing.name.clone()));
+            }
+            enums.push(EnumDef::new(name.clone(), values.to_vec()));
+            Some(FieldType::Enum(name))
+        };
+        let inline_enum = format!("{}{}", entity, pascal_case(field));
+
+        let declared = match &column.data_type {
+            DataType::Enum(values) => Some(declare(inline_enum.clone(), values)),
+            DataType::Custom(name, _) => {
+                let name = object_name(name);
+                let values = self.enum_types.iter().find(|(t, _)| *t == name).map(|(_, v)| v.clone());
+                values.map(|values| declare(pascal_case(&name), &values))
+            }
+            _ => None,
+        };
+        if let Some(declared) = declared {
+            if declared.is_none() {
+                self.warnings.push(format!("Values of enum column '{}' are not valid identifiers; it is stored as a String", field));
+            }
+            return Some((declared.unwrap_or(FieldType::String), None));
+        }
+
+        let rendered = column.data_type.to_string().to_ascii_lowercase().replace(" unsigned", "");
+        let (base, size) = match rendered.split_once('(') {
+            Some((base, rest)) => (base.trim().to_string(), rest.split([',', ')']).next()?.trim().parse::<i64>().ok()),
+            None => (rendered.trim().to_string(), None),
+        };
+        let field_type = match base.as_str() {
+            "tinyint" | "bit" if size == Some(1) => FieldType::Boolean,
+            "boolean" | "bool" => FieldType::Boolean,
+            "smallint" | "int" | "integer" | "mediumint" | "tinyint" | "int2" | "int4" | "serial" | "smallserial" => {
+                FieldType::Integer
+            }
+            "bigint" | "int8" | "bigserial" => FieldType::Long,
+            "real" | "float" | "float4" => FieldType::Float,
+            "double" | "double precision" | "float8" => FieldType::Double,
+            "decimal" | "numeric" | "dec" => FieldType::Decimal,
+            "uuid" => FieldType::Uuid,
+            "timestamp" | "timestamptz" | "timestamp with time zone" | "timestamp without time zone" | "datetime" => {
+                FieldType::DateTime
+            }
+            "date" => {
+                self.warnings.push(format!("Date column '{}' is stored as a DateTime", field));
+                FieldType::DateTime
+            }
+            "varchar" | "character varying" | "char" | "character" | "nvarchar" | "nchar" | "text" | "tinytext"
+            | "mediumtext" | "longtext" | "citext" | "clob" | "string" => {
+                if let Some(values) = &column.check_values {
+                    if let Some(field_type) = declare(inline_enum, values) {
+                        return Some((field_type, None));
+                    }
+                }
+                return Some((FieldType::String, size));
+            }
+            _ => return None,
+        };
+        Some((field_type, None))
+    }
+}
+
+impl Table {
+    fn add_column(&mut self, column: ColumnDef) {
+        let name = column.name.value.clone();
+        let mut not_null = false;
+        let mut check_values = None;
+        for option in column.options {
+            match option.option {
+                ColumnOption::NotNull => not_null = true,
+                ColumnOption::Unique { is_primary: true, .. } => {
+                    not_null = true;
+                    self.primary_key = vec![name.clone()];
+                }
+                ColumnOption::Unique { is_primary: false, .. } => self.unique.push(vec![name.clone()]),
+                ColumnOption::ForeignKey { foreign_table, .. } => {
+                    self.foreign_keys.push((vec![name.clone()], object_name(&foreign_table)))
+                }
+                ColumnOption::Check(expr) => check_values = in_list(&expr, &name),
+                _ => {}
+            }
+        }
+        self.columns.push(Column {
+            name,
+            data_type: column.data_type,
+            not_null,
+            check_values,
+        });
+    }
+
+    fn add_constraint(&mut self, constraint: TableConstraint) {
+        let names = |columns: Vec<sqlparser::ast::Ident>| columns.into_iter().map(|c| c.value).collect::<Vec<_>>();
+        match constraint {
+            TableConstraint::PrimaryKey { columns, .. } => self.primary_key = names(columns),
+            TableConstraint::Unique { columns, .. } => self.unique.push(names(columns)),
+            TableConstraint::ForeignKey {
+                columns, foreign_table, ..
+            } => self.foreign_keys.push((names(columns), object_name(&foreign_table))),
+            TableConstraint::Check { expr, .. } => {
+                for column in &mut self.columns {
+                    if let Some(values) = in_list(&expr, &column.name) {
+                        column.check_values = Some(values);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The string values of `column IN ('A', 'B')`.
+fn in_list(expr: &Expr, column: &str) -> Option<Vec<String>> {
+    let Expr::InList {
+        expr,
+        list,
+        negated: false,
+    } = expr
+    else {
+        return None;
+    };
+    if !matches!(expr.as_ref(), Expr::Identifier(ident) if ident.value == column) {
+        return None;
+    }
+    list.iter()
+        .map(|value| match value {
+            Expr::Value(Value::SingleQuotedString(s)) => Some(s.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The unqualified, unquoted name: `public.trucks` -> `trucks`.
+fn object_name(name: &ObjectName) -> String {
+    name.0.last().map(|ident| ident.value.clone()).unwrap_or_default()
+}
+
+fn is_user_table(table: &str) -> bool {
+    matches!(table.to_ascii_lowercase().as_str(), "users" | "user" | "jhi_user")
+}
+
+/// `licensePlate` and `LICENSE_PLATE` -> `license_plate`.
+fn field_name(column: &str) -> String {
+    snake_case(column)
+}
+
+fn is_enum_value(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_alphabetic()) && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,475 @@
+use crate::config::{Authentication, Database, Frontend, Infrastructure, Orm, ProjectConfig, RouterStrategy};
+use crate::generator::contract::path_parameters;
+use crate::generator::naming::{pluralize, rust_ident, snake_case};
+use crate::model::{FieldType, HttpMethod, ParameterLocation};
+use anyhow::{bail, Result};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+/// A single problem found in a `ProjectConfig`, pointing at the offending field
+/// and suggesting how to fix it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    pub field: String,
+    pub message: String,
+    pub hint: Option<String>,
+}
+
+impl ValidationIssue {
+    fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.field, self.message)?;
+        if let Some(hint) = &self.hint {
+            write!(f, "\n    hint: {}", hint)?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors make generation impossible; warnings describe combinations that
+/// generate, but probably not what the user expects.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    pub errors: Vec<ValidationIssue>,
+    pub warnings: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Turns the report into an error listing every problem if any error was found.
+    pub fn into_result(self) -> Result<()> {
+        if self.is_ok() {
+            return Ok(());
+        }
+        bail!("Invalid project configuration:\n{}", self)
+    }
+
+    fn error(&mut self, issue: ValidationIssue) {
+        self.errors.push(issue);
+    }
+
+    fn warning(&mut self, issue: ValidationIssue) {
+        self.warnings.push(issue);
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for issue in &self.errors {
+            writeln!(f, "  error {}", issue)?;
+        }
+        for issue in &self.warnings {
+            writeln!(f, "  warning {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl ProjectConfig {
+    /// Checks that the selected options can be generated together.
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        self.validate_name(&mut report);
+        self.validate_persistence(&mut report);
+        self.validate_infrastructure(&mut report);
+        self.validate_authentication(&mut report);
+        self.validate_frontend(&mut report);
+        self.validate_entities(&mut report);
+        self.validate_enums(&mut report);
+        self.validate_relationships(&mut report);
+        self.validate_contract(&mut report);
+        report
+    }
+
+    fn validate_name(&self, report: &mut ValidationReport) {
+        let valid = self.name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+            && self.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            report.error(
+                ValidationIssue::new("name", format!("'{}' is not a valid crate name", self.name))
+                    .hint("start with a letter and use only letters, digits, '-' and '_', e.g. 'my-axum-app'"),
+            );
+        }
+    }
+
+    fn validate_persistence(&self, report: &mut ValidationReport) {
+        match (self.database, self.orm) {
+            (Database::MongoDB, Orm::None) => self.validate_mongodb_ids(report),
+            (Database::MongoDB, orm) => report.error(
+                ValidationIssue::new("orm", format!("{} does not support MongoDB", orm))
+                    .hint("set orm to None to use the native MongoDB driver, or pick a SQL database"),
+            ),
+            (database, Orm::None) => report.error(
+                ValidationIssue::new("orm", format!("{} requires an ORM; the adapters have no native driver for it", database))
+                    .hint("set orm to Sqlx, Diesel or SeaOrm"),
+            ),
+            _ => {}
+        }
+    }
+
+    /// The native MongoDB adapters store the id the application generated.
+    fn validate_mongodb_ids(&self, report: &mut ValidationReport) {
+        let keys = std::iter::once(("primary_key".to_string(), self.primary_key)).chain(
+            self.entities.iter().filter_map(|e| e.primary_key.map(|key| (format!("entities.{}.primary_key", e.name), key))),
+        );
+        for (field, key) in keys.filter(|(_, key)| key.is_sequence()) {
+            report.error(
+                ValidationIssue::new(&field, format!("{} ids are assigned by a database sequence, which MongoDB does not have", key))
+                    .hint("use UuidV7, UuidV4 or Ulid, which the application generates"),
+            );
+        }
+    }
+
+    fn validate_infrastructure(&self, report: &mut ValidationReport) {
+        if self.infrastructure.contains(&Infrastructure::Socket) && self.router_strategy != RouterStrategy::Standard {
+            report.error(
+                ValidationIssue::new(
+                    "infrastructure",
+                    format!("Socket is only wired into the Standard router, not {}", self.router_strategy),
+                )
+                .hint("set router_strategy to Standard or remove Socket from infrastructure"),
+            );
+        }
+
+        let mut seen = HashSet::new();
+        for infra in &self.infrastructure {
+            if !seen.insert(infra.to_string()) {
+                report.warning(ValidationIssue::new("infrastructure", format!("{} is listed more than once", infra)));
+            }
+        }
+    }
+
+    fn validate_authentication(&self, report: &mut ValidationReport) {
+        if let Authentication::OAuth2(providers) = &self.authentication {
+            if providers.is_empty() {
+                report.error(
+                    ValidationIssue::new("authentication", "OAuth2 needs at least one provider")
+                        .hint("add a provider (Discord, Google, Apple or GitHub) or choose another strategy"),
+                );
+            }
+
+            let mut seen = HashSet::new();
+            for provider in providers {
+                if !seen.insert(provider.to_string()) {
+                    report.warning(ValidationIssue::new(
+                        "authentication",
+                        format!("OAuth2 provider {} is listed more than once", provider),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn validate_frontend(&self, report: &mut ValidationReport) {
+        if !matches!(self.frontend, Frontend::React | Frontend::None) {
+            report.warning(
+                ValidationIssue::new("frontend", format!("There are no {} templates yet; only a placeholder README is generated", self.frontend))
+                    .hint("choose React for a working client"),
+            );
+        }
+    }
+
+    fn validate_entities(&self, report: &mut ValidationReport) {
+        let mut names = HashSet::new();
+        for entity in &self.entities {
+            let field = format!("entities.{}", entity.name);
+            if !entity.name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+                || !entity.name.chars().all(|c| c.is_ascii_alphanumeric())
+            {
+                report.error(
+                    ValidationIssue::new(&field, format!("'{}' is not a valid entity name", entity.name))
+                        .hint("use PascalCase, e.g. 'DeliveryRoute'"),
+                );
+            }
+            if entity.name == "User" {
+                report.error(
+                    ValidationIssue::new(&field, "'User' is reserved for the built-in user and authentication slice")
+                        .hint("rename the entity, e.g. 'Customer'"),
+                );
+            }
+            if !names.insert(entity.name.as_str()) {
+                report.error(ValidationIssue::new(&field, format!("Entity '{}' is defined more than once", entity.name)));
+            }
+
+            if entity.fields.is_empty() {
+                report.error(
+                    ValidationIssue::new(&field, format!("Entity '{}' has no fields", entity.name))
+                        .hint(format!("add at least one, e.g. 'rhupster entity {} --field name:string'", entity.name)),
+                );
+            }
+
+            let mut fields = HashSet::new();
+            for f in &entity.fields {
+                if !is_snake_case(&f.name) {
+                    report.error(
+                        ValidationIssue::new(&field, format!("'{}' is not a valid field name", f.name))
+                            .hint("use snake_case, e.g. 'license_plate'"),
+                    );
+                }
+                if f.name == "id" {
+                    report.error(
+                        ValidationIssue::new(&field, "'id' is generated automatically")
+                            .hint("remove the 'id' field"),
+                    );
+                }
+                if !fields.insert(f.name.as_str()) {
+                    report.error(ValidationIssue::new(&field, format!("Field '{}' is defined more than once", f.name)));
+                }
+                if let FieldType::Enum(name) = &f.field_type {
+                    if !self.enums.iter().any(|e| &e.name == name) {
+                        report.error(
+                            ValidationIssue::new(&field, format!("Field '{}' uses undeclared enum '{}'", f.name, name))
+                                .hint(format!("declare it under [[enums]], e.g. name = \"{}\", values = [\"ACTIVE\", \"INACTIVE\"]", name)),
+                        );
+                    }
+                }
+                if f.field_type == FieldType::Decimal {
+                    if self.database == Database::SQLite {
+                        report.error(
+                            ValidationIssue::new(&field, format!("Field '{}' is a Decimal, which SQLite cannot store exactly", f.name))
+                                .hint("use Double, or pick Postgres or MySQL"),
+                        );
+                    }
+                    if f.min.is_some() || f.max.is_some() {
+                        report.error(
+                            ValidationIssue::new(&field, format!("Field '{}' is a Decimal, which cannot have min or max bounds", f.name))
+                                .hint("remove min and max, or check the bounds in the application service"),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_enums(&self, report: &mut ValidationReport) {
+        let mut names = HashSet::new();
+        for def in &self.enums {
+            let field = format!("enums.{}", def.name);
+            if !def.name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+                || !def.name.chars().all(|c| c.is_ascii_alphanumeric())
+            {
+                report.error(
+                    ValidationIssue::new(&field, format!("'{}' is not a valid enum name", def.name))
+                        .hint("use PascalCase, e.g. 'TruckStatus'"),
+                );
+            }
+            let builtin = FieldType::ALL.iter().any(|t| t.to_string().eq_ignore_ascii_case(&def.name));
+            if builtin || def.name == "User" || self.entities.iter().any(|e| e.name == def.name) || !names.insert(def.name.as_str()) {
+                report.error(ValidationIssue::new(&field, format!("'{}' is already the name of a type", def.name)));
+            }
+
+            if def.values.is_empty() {
+                report.error(ValidationIssue::new(&field, format!("Enum '{}' has no values", def.name)));
+            }
+            let mut values = HashSet::new();
+            for value in &def.values {
+                if !value.starts_with(|c: char| c.is_ascii_alphabetic())
+                    || !value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    report.error(
+                        ValidationIssue::new(&field, format!("'{}' is not a valid enum value", value))
+                            .hint("use letters, digits and '_', e.g. 'IN_TRANSIT'"),
+                    );
+                }
+                if !values.insert(value.to_ascii_lowercase().replace('_', "")) {
+                    report.error(ValidationIssue::new(&field, format!("Value '{}' is listed more than once", value)));
+                }
+            }
+            for value in def.wire_values.keys().filter(|v| !def.values.contains(v)) {
+                report.error(ValidationIssue::new(&field, format!("'{}' has a wire value but is not a value of the enum", value)));
+            }
+        }
+    }
+
+    fn validate_relationships(&self, report: &mut ValidationReport) {
+        let known = |name: &str| name == "User" || self.entities.iter().any(|e| e.name == name);
+        let mut relation_fields = HashSet::new();
+        for (i, relationship) in self.relationships.iter().enumerate() {
+            let field = format!("relationships[{}]", i);
+            for name in [&relationship.from, &relationship.to] {
+                if !known(name) {
+                    report.error(
+                        ValidationIssue::new(&field, format!("Relationship refers to unknown entity '{}'", name))
+                            .hint("add the entity first or point the relationship at 'User'"),
+                    );
+                }
+            }
+            if relationship.owner() == "User" {
+                report.error(
+                    ValidationIssue::new(&field, "Relationships cannot be stored on the built-in 'User'")
+                        .hint("declare it from the other side, e.g. ManyToOne { Truck{driver} to User }"),
+                );
+                continue;
+            }
+
+            let relation_field = relationship
+                .owner_field()
+                .map(String::from)
+                .unwrap_or_else(|| snake_case(relationship.target()));
+            if !is_snake_case(&relation_field) {
+                report.error(
+                    ValidationIssue::new(&field, format!("'{}' is not a valid relationship field name", relation_field))
+                        .hint("use snake_case, e.g. 'driver'"),
+                );
+            }
+            let owner = self.entities.iter().find(|e| e.name == relationship.owner());
+            let clashes = owner.is_some_and(|e| {
+                e.fields.iter().any(|f| f.name == relation_field || f.name == format!("{}_id", relation_field))
+            });
+            if clashes || !relation_fields.insert((relationship.owner(), relation_field.clone())) {
+                report.error(
+                    ValidationIssue::new(
+                        &field,
+                        format!("'{}' already has a field named '{}'", relationship.owner(), relation_field),
+                    )
+                    .hint("name the relationship field explicitly, e.g. Truck{co_driver} to User"),
+                );
+            }
+        }
+    }
+
+    fn validate_contract(&self, report: &mut ValidationReport) {
+        let mut schema_names = HashSet::new();
+        for schema in &self.contract.schemas {
+            let field = format!("contract.schemas.{}", schema.name);
+            if !schema.name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+                || !schema.name.chars().all(|c| c.is_ascii_alphanumeric())
+            {
+                report.error(
+                    ValidationIssue::new(&field, format!("'{}' is not a valid schema name", schema.name))
+                        .hint("use PascalCase, e.g. 'TruckLocation'"),
+                );
+            }
+            if !schema_names.insert(schema.name.as_str()) {
+                report.error(ValidationIssue::new(&field, format!("Schema '{}' is defined more than once", schema.name)));
+            }
+            let mut fields = HashSet::new();
+            for property in &schema.properties {
+                if !fields.insert(rust_ident(&property.name)) {
+                    report.error(ValidationIssue::new(
+                        &field,
+                        format!("Property '{}' clashes with another property of '{}'", property.name, schema.name),
+                    ));
+                }
+                self.validate_contract_type(report, &field, &property.schema_type);
+            }
+        }
+
+        // Generated endpoints of every entity, by path with the parameters left out.
+        let mut routes: HashMap<(HttpMethod, String), String> = HashMap::new();
+        for entity in &self.entities {
+            let collection = format!("/api/{}", pluralize(&snake_case(&entity.name)).replace('_', "-"));
+            let item = format!("{}/{{}}", collection);
+            let mut endpoints = vec![(HttpMethod::Post, &collection), (HttpMethod::Get, &collection), (HttpMethod::Get, &item)];
+            if self.router_strategy != RouterStrategy::Standard {
+                endpoints.extend([(HttpMethod::Put, &item), (HttpMethod::Delete, &item)]);
+            }
+            for (method, path) in endpoints {
+                routes.insert((method, path.clone()), format!("the generated {} endpoints", entity.name));
+            }
+        }
+
+        let mut names = HashSet::new();
+        let mut parameter_names: HashMap<String, Vec<String>> = HashMap::new();
+        for operation in &self.contract.operations {
+            let field = format!("contract.operations.{}", operation.name);
+            let endpoint = format!("{} {}", operation.method, operation.path);
+            if !is_snake_case(&operation.name) {
+                report.error(
+                    ValidationIssue::new(&field, format!("'{}' is not a valid handler name", operation.name))
+                        .hint("use snake_case, e.g. 'assign_driver'"),
+                );
+            }
+            if !names.insert(operation.name.as_str()) {
+                report.error(ValidationIssue::new(&field, format!("Operation '{}' is defined more than once", operation.name)));
+            }
+            if !operation.path.starts_with('/') {
+                report.error(
+                    ValidationIssue::new(&field, format!("Path '{}' does not start with '/'", operation.path))
+                        .hint("write it as in the OpenAPI document, e.g. '/trucks/{truckId}'"),
+                );
+            }
+
+            let parameters = path_parameters(&operation.path);
+            for name in &parameters {
+                let declared = operation
+                    .parameters
+                    .iter()
+                    .any(|p| p.location == ParameterLocation::Path && &p.name == name);
+                if !declared {
+                    report.error(ValidationIssue::new(
+                        &field,
+                        format!("Path parameter '{}' of {} is not declared", name, endpoint),
+                    ));
+                }
+            }
+            let shape = operation
+                .path
+                .split('/')
+                .map(|segment| if segment.starts_with('{') { "{}" } else { segment })
+                .collect::<Vec<_>>()
+                .join("/");
+            match parameter_names.get(&shape) {
+                Some(existing) if existing != &parameters => report.error(
+                    ValidationIssue::new(&field, format!("{} names its path parameters differently than another operation on the same path", endpoint))
+                        .hint("use the same parameter names for every method of a path"),
+                ),
+                Some(_) => {}
+                None => {
+                    parameter_names.insert(shape.clone(), parameters);
+                }
+            }
+            if let Some(owner) = routes.insert((operation.method, shape), endpoint.clone()) {
+                report.error(
+                    ValidationIssue::new(&field, format!("{} is already served by {}", endpoint, owner))
+                        .hint("give the operation another path or method"),
+                );
+            }
+
+            for parameter in &operation.parameters {
+                self.validate_contract_type(report, &field, &parameter.schema_type);
+            }
+            let bodies = operation.request_body.iter().chain(operation.responses.iter().filter_map(|r| r.body.as_ref()));
+            for body in bodies {
+                self.validate_contract_type(report, &field, body);
+            }
+        }
+    }
+
+    fn validate_contract_type(&self, report: &mut ValidationReport, field: &str, schema_type: &str) {
+        let name = schema_type.trim_end_matches("[]");
+        let known = name == "Json"
+            || FieldType::from_str(name).is_ok()
+            || self.enums.iter().any(|def| def.name == name)
+            || self.contract.schemas.iter().any(|s| s.name == name);
+        if !known {
+            report.error(
+                ValidationIssue::new(field, format!("Unknown type '{}'", schema_type))
+                    .hint("use a field type, a declared enum, a contract schema or 'Json'"),
+            );
+        }
+    }
+}
+
+fn is_snake_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
@@ -0,0 +1,198 @@
+use rhupster_core::config::{
+    Authentication, Database, Frontend, Infrastructure, OAuthProvider, Orm, PartialProjectConfig, ProjectConfig,
+    RouterStrategy,
+};
+use rhupster_core::model::PrimaryKey;
+use rhupster_core::presets::Presets;
+use std::path::PathBuf;
+
+fn write_config(file_name: &str, content: &str) -> PathBuf {
+    let dir = PathBuf::from("test_output/configs");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(file_name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn test_load_toml_config_with_defaults() {
+    let path = write_config(
+        "partial.toml",
+        r#"
+name = "fleet-service"
+database = "SQLite"
+"#,
+    );
+
+    let config = ProjectConfig::from_file(&path).unwrap();
+    let defaults = ProjectConfig::default();
+
+    assert_eq!(config.name, "fleet-service");
+    assert_eq!(config.database, Database::SQLite);
+    // Everything else falls back to the wizard defaults
+    assert_eq!(config.orm, defaults.orm);
+    assert_eq!(config.router_strategy, defaults.router_strategy);
+    assert_eq!(config.authentication, defaults.authentication);
+    assert_eq!(config.devops.docker_compose, defaults.devops.docker_compose);
+}
+
+#[test]
+fn test_load_yaml_config() {
+    let path = write_config(
+        "oauth.yaml",
+        r#"
+name: yaml-app
+orm: Diesel
+router_strategy: AxumController
+authentication: !OAuth2 [Google, GitHub]
+devops:
+  docker_compose: false
+"#,
+    );
+
+    let config = ProjectConfig::from_file(&path).unwrap();
+
+    assert_eq!(config.name, "yaml-app");
+    assert_eq!(config.orm, Orm::Diesel);
+    assert_eq!(config.router_strategy, RouterStrategy::AxumController);
+    assert_eq!(
+        config.authentication,
+        Authentication::OAuth2(vec![OAuthProvider::Google, OAuthProvider::GitHub])
+    );
+    assert!(!config.devops.docker_compose);
+}
+
+#[test]
+fn test_load_json_config() {
+    let path = write_config("basic.json", r#"{ "name": "json-app", "authentication": "Basic", "hateoas": true }"#);
+
+    let config = ProjectConfig::from_file(&path).unwrap();
+
+    assert_eq!(config.name, "json-app");
+    assert_eq!(config.authentication, Authentication::Basic);
+    assert!(config.hateoas);
+}
+
+#[test]
+fn test_load_config_rejects_unknown_extension() {
+    let path = write_config("config.ini", "name = nope");
+
+    let err = ProjectConfig::from_file(&path).unwrap_err();
+
+    assert!(err.to_string().contains("Unsupported config file"));
+}
+
+#[test]
+fn test_parse_option_names() {
+    assert_eq!("postgres".parse::<Database>().unwrap(), Database::Postgres);
+    assert_eq!("SeaORM".parse::<Orm>().unwrap(), Orm::SeaOrm);
+    assert_eq!("axum-controller".parse::<RouterStrategy>().unwrap(), RouterStrategy::AxumController);
+    assert_eq!("oauth2".parse::<Authentication>().unwrap(), Authentication::OAuth2(vec![]));
+    assert!("oracle".parse::<Database>().is_err());
+}
+
+#[test]
+fn test_partial_config_merge_and_defaults() {
+    let file = PartialProjectConfig {
+        name: Some("from-file".to_string()),
+        database: Some(Database::MySQL),
+        ..Default::default()
+    };
+    let flags = PartialProjectConfig {
+        database: Some(Database::MongoDB),
+        hateoas: Some(true),
+        ..Default::default()
+    };
+
+    let config = file.merge(flags).into_config();
+
+    assert_eq!(config.name, "from-file");
+    assert_eq!(config.database, Database::MongoDB);
+    assert!(config.hateoas);
+    // MongoDB never gets an ORM unless one was asked for explicitly
+    assert_eq!(config.orm, Orm::None);
+    // nor ids from a sequence, which it does not have
+    assert_eq!(config.primary_key, PrimaryKey::UuidV7);
+    assert_eq!(config.api_ui, ProjectConfig::default().api_ui);
+}
+
+#[test]
+fn test_load_mongodb_config_without_orm() {
+    let path = write_config("mongo.toml", r#"database = "MongoDB""#);
+
+    let config = ProjectConfig::from_file(&path).unwrap();
+
+    assert_eq!(config.orm, Orm::None);
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_builtin_presets() {
+    let presets = Presets::builtin();
+    assert_eq!(presets.names().collect::<Vec<_>>(), vec!["event-driven", "minimal", "monolith"]);
+
+    let minimal = presets.get("minimal").unwrap().clone().into_config();
+    assert_eq!(minimal.database, Database::SQLite);
+    assert_eq!(minimal.orm, Orm::Sqlx);
+    assert_eq!(minimal.frontend, Frontend::None);
+    assert_eq!(minimal.authentication, Authentication::None);
+
+    let event_driven = presets.get("event-driven").unwrap().clone().into_config();
+    assert_eq!(event_driven.infrastructure, vec![Infrastructure::Kafka, Infrastructure::Redis]);
+    assert!(event_driven.validate().is_ok());
+
+    let err = presets.get("huge").unwrap_err().to_string();
+    assert!(err.contains("minimal, monolith"), "{}", err);
+}
+
+#[test]
+fn test_user_presets_merge_with_builtin_ones() {
+    let path = write_config(
+        "presets.toml",
+        r#"
+[presets.payments]
+database = "MySQL"
+orm = "SeaOrm"
+infrastructure = ["Redis"]
+
+[presets.minimal]
+database = "Postgres"
+"#,
+    );
+
+    let presets = Presets::load(Some(&path)).unwrap();
+    assert_eq!(presets.names().count(), 4);
+    assert_eq!(presets.get("payments").unwrap().orm, Some(Orm::SeaOrm));
+    // A user preset replaces the built-in one of the same name
+    assert_eq!(presets.get("minimal").unwrap().database, Some(Database::Postgres));
+    assert_eq!(presets.get("minimal").unwrap().frontend, None);
+
+    // A missing user file just means no user presets
+    let builtin_only = Presets::load(Some(&PathBuf::from("test_output/configs/missing.toml"))).unwrap();
+    assert_eq!(builtin_only, Presets::builtin());
+}
+
+#[test]
+fn test_flags_override_preset() {
+    let preset = Presets::builtin().get("monolith").unwrap().clone();
+    let flags = PartialProjectConfig {
+        database: Some(Database::MySQL),
+        ..PartialProjectConfig::default()
+    };
+
+    let config = preset.merge(flags).into_config();
+    assert_eq!(config.database, Database::MySQL);
+    assert_eq!(config.frontend, Frontend::React);
+}
+
+#[test]
+fn test_oauth_providers_combine_only_with_oauth2() {
+    let providers = Some(vec![OAuthProvider::Google]);
+    let oauth2 = Some(Authentication::OAuth2(vec![OAuthProvider::Google]));
+    assert_eq!(Authentication::with_providers(None, providers.clone()).unwrap(), oauth2);
+    assert_eq!(Authentication::with_providers(Some(Authentication::OAuth2(Vec::new())), providers.clone()).unwrap(), oauth2);
+    assert_eq!(Authentication::with_providers(Some(Authentication::Jwt), None).unwrap(), Some(Authentication::Jwt));
+
+    let err = Authentication::with_providers(Some(Authentication::Jwt), providers).unwrap_err();
+    assert!(err.to_string().contains("only apply to oauth2"), "{}", err);
+}
@@ -0,0 +1,73 @@
+use rhupster_core::config::ProjectConfig;
+use rhupster_core::generator::{ConflictError, ConflictPolicy, Generator, TemplateSource};
+use std::path::PathBuf;
+use tokio::fs;
+
+async fn generate_fresh(output_dir: &PathBuf) -> Generator<'static> {
+    let _ = fs::remove_dir_all(output_dir).await;
+    let config = ProjectConfig {
+        name: "conflict-app".to_string(),
+        ..ProjectConfig::default()
+    };
+    let generator = Generator::new(config, TemplateSource::Path(PathBuf::from("../templates")));
+    let summary = generator.generate(output_dir).await.unwrap();
+    assert!(summary.overwritten.is_empty() && summary.skipped.is_empty());
+    generator
+}
+
+#[tokio::test]
+async fn test_regenerating_untouched_project_reports_unchanged() {
+    let output_dir = PathBuf::from("test_output/conflicts_unchanged");
+    let generator = generate_fresh(&output_dir).await;
+
+    let summary = generator.generate(&output_dir).await.unwrap();
+    assert!(summary.created.is_empty());
+    assert!(!summary.unchanged.is_empty());
+}
+
+#[tokio::test]
+async fn test_modified_file_fails_by_default_and_nothing_is_written() {
+    let output_dir = PathBuf::from("test_output/conflicts_fail");
+    let generator = generate_fresh(&output_dir).await;
+
+    let readme = output_dir.join("README.md");
+    let removed = output_dir.join("Cargo.toml");
+    fs::write(&readme, "my notes").await.unwrap();
+    fs::remove_file(&removed).await.unwrap();
+
+    let err = generator.generate(&output_dir).await.unwrap_err();
+    let conflict = err.downcast_ref::<ConflictError>().expect("a refused conflict is reported as a ConflictError");
+    assert_eq!(conflict, &ConflictError { path: readme.clone(), policy: ConflictPolicy::Fail });
+    assert!(!err.to_string().contains("--force"), "the CLI words the hint: {}", err);
+    assert_eq!(fs::read_to_string(&readme).await.unwrap(), "my notes");
+    assert!(!removed.exists(), "no file may be written when a conflict is refused");
+}
+
+#[tokio::test]
+async fn test_skip_existing_keeps_edits_and_force_overwrites_them() {
+    let output_dir = PathBuf::from("test_output/conflicts_policies");
+    let generator = generate_fresh(&output_dir).await;
+
+    let readme = output_dir.join("README.md");
+    fs::write(&readme, "my notes").await.unwrap();
+
+    let summary = generator.generate_with(&output_dir, &mut ConflictPolicy::Skip).await.unwrap();
+    assert_eq!(summary.skipped, vec![readme.clone()]);
+    assert_eq!(fs::read_to_string(&readme).await.unwrap(), "my notes");
+
+    let summary = generator.generate_with(&output_dir, &mut ConflictPolicy::Overwrite).await.unwrap();
+    assert_eq!(summary.overwritten, vec![readme.clone()]);
+    assert_ne!(fs::read_to_string(&readme).await.unwrap(), "my notes");
+}
+
+#[tokio::test]
+async fn test_unreadable_existing_file_fails_instead_of_counting_as_created() {
+    let output_dir = PathBuf::from("test_output/conflicts_unreadable");
+    let generator = generate_fresh(&output_dir).await;
+    let readme = output_dir.join("README.md");
+    fs::remove_file(&readme).await.unwrap();
+    fs::create_dir(&readme).await.unwrap();
+
+    let err = generator.generate(&output_dir).await.unwrap_err();
+    assert!(err.to_string().contains("Failed to read") && err.to_string().contains("README.md"), "{}", err);
+}
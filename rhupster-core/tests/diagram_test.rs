@@ -0,0 +1,48 @@
+use rhupster_core::diagram::{self, DiagramFormat};
+use rhupster_core::dsl;
+use rhupster_core::model::PrimaryKey;
+
+const FLEET: &str = r#"
+enum TruckStatus { AVAILABLE, IN_TRANSIT }
+
+entity Truck {
+    license_plate String required unique
+    status TruckStatus
+}
+
+entity Route {
+    label String
+}
+
+relationship ManyToOne {
+    Truck{driver required} to User
+}
+
+relationship ManyToMany {
+    Truck{routes} to Route
+}
+"#;
+
+#[test]
+fn test_mermaid_lists_columns_and_relationships() {
+    let model = dsl::parse(FLEET).unwrap();
+    let mermaid = diagram::mermaid(&model, PrimaryKey::UuidV7);
+
+    assert!(mermaid.starts_with("erDiagram\n"), "{}", mermaid);
+    assert!(mermaid.contains("    Truck {\n        uuid id PK\n        string license_plate UK \"required\"\n        TruckStatus status\n        uuid driver_id FK \"required\"\n    }"), "{}", mermaid);
+    assert!(mermaid.contains("    User {\n        uuid id PK\n    }"), "referenced entities get a box: {}", mermaid);
+    assert!(mermaid.contains("    Truck }o--|| User : driver\n"), "{}", mermaid);
+    assert!(mermaid.contains("    Truck }o--o{ Route : routes\n"), "{}", mermaid);
+    assert!(!mermaid.contains("routes_id"), "many-to-many relationships have no foreign key column");
+}
+
+#[test]
+fn test_plantuml_wraps_entities_in_a_document() {
+    let model = dsl::parse(FLEET).unwrap();
+    let plantuml = diagram::render(&model, PrimaryKey::U64, "puml".parse::<DiagramFormat>().unwrap());
+
+    assert!(plantuml.starts_with("@startuml\n") && plantuml.ends_with("@enduml\n"), "{}", plantuml);
+    assert!(plantuml.contains("entity Route {\n  *id : u64 <<PK>>\n  --\n  label : string\n}"), "{}", plantuml);
+    assert!(plantuml.contains("Truck }o--|| User : driver\n"), "{}", plantuml);
+    assert!("svg".parse::<DiagramFormat>().is_err());
+}
@@ -0,0 +1,86 @@
+use rhupster_core::dsl::{self, Span};
+use rhupster_core::model::{FetchType, FieldType, RelationshipKind};
+
+const FLEET: &str = r#"
+// Fleet domain
+enum TruckStatus { AVAILABLE, IN_TRANSIT, MAINTENANCE }
+
+/* Trucks are driven by users */
+entity Truck {
+    license_plate String required unique pattern(/^[A-Z0-9-]+$/)
+    capacity Float min(0) max(40000)
+    status TruckStatus required
+    serial UUID
+}
+
+entity Product {
+    name String required min(1) max(120), price Decimal
+}
+
+relationship ManyToOne {
+    Truck{driver required eager} to User
+}
+
+relationship OneToMany {
+    Truck{products} to Product{truck}
+}
+"#;
+
+#[test]
+fn test_parse_entities_enums_and_relationships() {
+    let model = dsl::parse(FLEET).unwrap();
+
+    assert_eq!(model.enums.len(), 1);
+    assert_eq!(model.enums[0].values, vec!["AVAILABLE", "IN_TRANSIT", "MAINTENANCE"]);
+
+    let truck = &model.entities[0];
+    assert_eq!(truck.name, "Truck");
+    let plate = &truck.fields[0];
+    assert_eq!(plate.field_type, FieldType::String);
+    assert!(plate.required && plate.unique);
+    assert_eq!(plate.pattern.as_deref(), Some("^[A-Z0-9-]+$"));
+    assert_eq!((truck.fields[1].min, truck.fields[1].max), (Some(0), Some(40000)));
+    assert_eq!(truck.fields[2].field_type, FieldType::Enum("TruckStatus".to_string()));
+    assert_eq!(truck.fields[3].field_type, FieldType::Uuid);
+
+    let product = &model.entities[1];
+    assert_eq!(product.fields.len(), 2);
+    assert_eq!(product.fields[1].field_type, FieldType::Decimal);
+
+    let driver = &model.relationships[0];
+    assert_eq!(driver.kind, RelationshipKind::ManyToOne);
+    assert_eq!((driver.from.as_str(), driver.to.as_str()), ("Truck", "User"));
+    assert_eq!(driver.from_field.as_deref(), Some("driver"));
+    assert!(driver.required);
+    assert_eq!(driver.fetch, FetchType::Eager);
+    assert_eq!(model.relationships[1].fetch, FetchType::Lazy);
+    assert_eq!(model.relationships[1].to_field.as_deref(), Some("truck"));
+}
+
+#[test]
+fn test_errors_carry_line_and_column() {
+    let err = dsl::parse("entity Truck {\n    capacity Blob\n}").unwrap_err();
+    assert_eq!(err.span, Span { line: 2, column: 14 });
+    assert!(err.message.contains("Unknown type 'Blob'"), "{}", err);
+
+    let err = dsl::parse("entity Truck {\n    plate String requird\n}").unwrap_err();
+    assert_eq!(err.span, Span { line: 2, column: 18 });
+    assert!(err.message.contains("Unknown constraint 'requird'"));
+
+    let err = dsl::parse("entity Truck {\n  active Boolean min(1)\n}").unwrap_err();
+    assert_eq!(err.span, Span { line: 2, column: 18 });
+
+    let err = dsl::parse("entity Truck\nrelationship OneToMany { Truck to Trailer }").unwrap_err();
+    assert_eq!(err.span, Span { line: 2, column: 35 });
+    assert!(err.message.contains("unknown entity 'Trailer'"));
+
+    let err = dsl::parse("entity Truck\nentity Truck").unwrap_err();
+    assert_eq!(err.span, Span { line: 2, column: 8 });
+
+    let err = dsl::parse("entity Truck {\n  plate String pattern(\"[A-Z\n}").unwrap_err();
+    assert!(err.message.contains("Unterminated string"));
+
+    let err = dsl::parse("entity Truck { plate String }\nservice Truck").unwrap_err();
+    assert_eq!(err.span, Span { line: 2, column: 1 });
+    assert_eq!(err.to_string(), "line 2, column 1: Expected 'entity', 'enum' or 'relationship', found 'service'");
+}
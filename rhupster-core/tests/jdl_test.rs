@@ -0,0 +1,136 @@
+use rhupster_core::config::{Database, Frontend, Infrastructure, Orm};
+use rhupster_core::dsl::Span;
+use rhupster_core::jdl;
+use rhupster_core::model::{FieldType, RelationshipKind};
+
+const FLEET: &str = r#"
+application {
+  config {
+    baseName fleetService
+    packageName com.example.fleet
+    prodDatabaseType postgresql
+    authenticationType jwt
+    clientFramework react
+    cacheProvider redis
+  }
+  entities *
+}
+
+enum TruckStatus { AVAILABLE, IN_TRANSIT (In transit) }
+
+@paginate(pagination)
+entity Truck (trucks) {
+  licensePlate String required maxlength(20) pattern(/^[A-Z0-9-]+$/)
+  capacityKg Integer min(0)
+  status TruckStatus required
+  photo ImageBlob
+}
+
+entity DeliveryRoute {
+  name String required unique
+  distance BigDecimal
+}
+
+relationship ManyToOne {
+  Truck{driver(login) required} to User with builtInEntity
+  DeliveryRoute{truck} to Truck
+}
+
+dto * with mapstruct
+service all with serviceImpl
+paginate DeliveryRoute with infinite-scroll
+
+deployment {
+  deploymentType docker-compose
+}
+"#;
+
+#[test]
+fn test_import_maps_application_entities_and_relationships() {
+    let import = jdl::import(FLEET).unwrap();
+    let config = &import.config;
+
+    assert_eq!(config.name.as_deref(), Some("fleet-service"));
+    assert_eq!(config.database, Some(Database::Postgres));
+    assert_eq!(config.frontend, Some(Frontend::React));
+    assert_eq!(config.infrastructure, Some(vec![Infrastructure::Redis]));
+
+    let enums = config.enums.as_ref().unwrap();
+    assert_eq!(enums[0].values, vec!["AVAILABLE", "IN_TRANSIT"]);
+
+    let entities = config.entities.as_ref().unwrap();
+    let truck = &entities[0];
+    let names: Vec<&str> = truck.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["license_plate", "capacity_kg", "status"]);
+    assert!(truck.fields[0].required);
+    assert_eq!(truck.fields[0].max, Some(20));
+    assert_eq!(truck.fields[0].pattern.as_deref(), Some("^[A-Z0-9-]+$"));
+    assert_eq!(truck.fields[2].field_type, FieldType::Enum("TruckStatus".to_string()));
+    assert_eq!(entities[1].fields[1].field_type, FieldType::Decimal);
+    assert!(entities[1].fields[0].unique);
+
+    let relationships = config.relationships.as_ref().unwrap();
+    assert_eq!(relationships.len(), 2);
+    assert_eq!(relationships[0].kind, RelationshipKind::ManyToOne);
+    assert_eq!((relationships[0].from.as_str(), relationships[0].to.as_str()), ("Truck", "User"));
+    assert_eq!(relationships[0].from_field.as_deref(), Some("driver"));
+    assert!(relationships[0].required);
+
+    // The imported answers are a valid project once the wizard defaults are filled in.
+    assert!(import.config.clone().into_config().validate().is_ok());
+}
+
+#[test]
+fn test_unsupported_constructs_are_reported_as_warnings() {
+    let import = jdl::import(FLEET).unwrap();
+    let messages: Vec<String> = import.warnings.iter().map(|w| w.to_string()).collect();
+
+    let expected = [
+        "line 5, column 5: Config option 'packageName'",
+        "line 14, column 43: Custom value of 'IN_TRANSIT'",
+        "line 16, column 1: Pagination is not generated",
+        "line 17, column 15: Custom table name 'trucks'",
+        "line 21, column 9: Field 'photo' has unsupported type 'ImageBlob'",
+        "line 30, column 16: Display field 'login'",
+        "line 36, column 1: Pagination is not generated",
+        "line 38, column 1: 'deployment' is not supported",
+    ];
+    assert_eq!(messages.len(), expected.len(), "{:#?}", messages);
+    for (message, expected) in messages.iter().zip(expected) {
+        assert!(message.starts_with(expected), "{} does not start with {}", message, expected);
+    }
+}
+
+#[test]
+fn test_mongodb_applications_and_entity_filters() {
+    let source = r#"
+application {
+  config {
+    baseName shop
+    databaseType mongodb
+    authenticationType session
+  }
+  entities Product
+}
+entity Product { title String }
+entity Order { total Double }
+relationship OneToMany { Order{lines} to Product }
+"#;
+    let import = jdl::import(source).unwrap();
+    assert_eq!(import.config.database, Some(Database::MongoDB));
+    assert_eq!(import.config.orm, Some(Orm::None));
+    assert_eq!(import.config.entities.as_ref().unwrap().len(), 1);
+    assert!(import.config.relationships.as_ref().unwrap().is_empty());
+    assert_eq!(import.warnings.len(), 3, "{:#?}", import.warnings);
+    assert!(import.warnings[0].message.contains("authenticationType 'session'"));
+}
+
+#[test]
+fn test_import_errors_carry_line_and_column() {
+    let err = jdl::import("entity Truck {\n  plate String maxlength(x)\n}").unwrap_err();
+    assert_eq!(err.span, Span { line: 2, column: 26 });
+    assert!(err.message.contains("Expected a whole number for maxlength"), "{}", err);
+
+    let err = jdl::import("relationship ManyToOne {\n  Truck from User\n}").unwrap_err();
+    assert_eq!(err.span, Span { line: 2, column: 9 });
+}
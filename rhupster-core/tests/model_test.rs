@@ -0,0 +1,32 @@
+use rhupster_core::model::{Field, FieldType, PrimaryKey};
+
+#[test]
+fn test_parse_field_shorthand() {
+    let field: Field = "license_plate:string!".parse().unwrap();
+    assert_eq!(field.name, "license_plate");
+    assert_eq!(field.field_type, FieldType::String);
+    assert!(field.required);
+
+    let field: Field = "capacity:Float".parse().unwrap();
+    assert_eq!(field.field_type, FieldType::Float);
+    assert!(!field.required);
+
+    let field: Field = "status:TruckStatus!".parse().unwrap();
+    assert_eq!(field.field_type, FieldType::Enum("TruckStatus".to_string()));
+}
+
+#[test]
+fn test_parse_field_shorthand_errors() {
+    assert!("capacity".parse::<Field>().is_err());
+    assert!(":string".parse::<Field>().is_err());
+    assert!("capacity:blob".parse::<Field>().is_err());
+}
+
+#[test]
+fn test_parse_primary_key() {
+    assert_eq!("u64".parse::<PrimaryKey>().unwrap(), PrimaryKey::U64);
+    assert_eq!("uuid-v4".parse::<PrimaryKey>().unwrap(), PrimaryKey::UuidV4);
+    assert_eq!("UuidV7".parse::<PrimaryKey>().unwrap(), PrimaryKey::UuidV7);
+    assert_eq!("ulid".parse::<PrimaryKey>().unwrap(), PrimaryKey::Ulid);
+    assert!("serial".parse::<PrimaryKey>().is_err());
+}
@@ -0,0 +1,140 @@
+use rhupster_core::model::{FieldType, HttpMethod, ParameterLocation, RelationshipKind};
+use rhupster_core::openapi;
+
+const FLEET: &str = r##"
+openapi: 3.0.3
+info:
+  title: Fleet API
+  version: 1.0.0
+paths:
+  /trucks/{truckId}/assign:
+    parameters:
+      - {name: truckId, in: path, required: true, description: Truck to assign, schema: {type: integer, format: int64}}
+    post:
+      operationId: assignDriver
+      tags: [Dispatch]
+      summary: Assign a driver to a truck
+      requestBody:
+        content:
+          application/json:
+            schema: {$ref: '#/components/schemas/Assignment'}
+      responses:
+        '200':
+          description: The updated truck
+          content:
+            application/json:
+              schema: {$ref: '#/components/schemas/Truck'}
+        '404': {description: Truck not found}
+  /trucks:
+    get:
+      parameters:
+        - {name: status, in: query, schema: {$ref: '#/components/schemas/TruckStatus'}}
+        - {name: session, in: cookie, schema: {type: string}}
+      responses:
+        '200':
+          description: All trucks
+          content:
+            application/json:
+              schema: {type: array, items: {$ref: '#/components/schemas/Truck'}}
+components:
+  schemas:
+    TruckStatus:
+      type: string
+      enum: [AVAILABLE, IN_TRANSIT, in-repair]
+    Truck:
+      type: object
+      required: [licensePlate]
+      properties:
+        id: {type: integer, format: int64}
+        licensePlate: {type: string, minLength: 3, maxLength: 20}
+        status: {$ref: '#/components/schemas/TruckStatus'}
+        fuel: {type: string, enum: [DIESEL, ELECTRIC]}
+        driver: {$ref: '#/components/schemas/User'}
+        stops: {type: array, items: {$ref: '#/components/schemas/Stop'}}
+        tags: {type: array, items: {type: string}}
+    Stop:
+      type: object
+      properties:
+        arrivesAt: {type: string, format: date-time}
+    Assignment:
+      type: object
+      required: [driverId]
+      properties:
+        driverId: {type: string, format: uuid}
+    User:
+      type: object
+      properties:
+        login: {type: string}
+"##;
+
+#[test]
+fn test_import_schemas_as_entities() {
+    let import = openapi::import(FLEET).unwrap();
+    let config = &import.config;
+    assert_eq!(config.name.as_deref(), Some("fleet-api"));
+
+    let entities = config.entities.as_ref().unwrap();
+    let names: Vec<&str> = entities.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["Assignment", "Stop", "Truck"]);
+
+    let truck = &entities[2];
+    let fields: Vec<&str> = truck.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(fields, vec!["fuel", "license_plate", "status"]);
+    assert_eq!(truck.fields[0].field_type, FieldType::Enum("TruckFuel".to_string()));
+    assert!(truck.fields[1].required);
+    assert_eq!((truck.fields[1].min, truck.fields[1].max), (Some(3), Some(20)));
+    assert_eq!(entities[0].fields[0].field_type, FieldType::Uuid);
+
+    let enums: Vec<&str> = config.enums.as_ref().unwrap().iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(enums, vec!["TruckFuel", "TruckStatus"]);
+    let status = &config.enums.as_ref().unwrap()[1];
+    assert_eq!(status.values, vec!["AVAILABLE", "IN_TRANSIT", "in_repair"]);
+    assert_eq!(status.wire_value("in_repair"), "in-repair");
+    assert_eq!(status.wire_value("IN_TRANSIT"), "IN_TRANSIT");
+
+    let relationships = config.relationships.as_ref().unwrap();
+    assert_eq!(relationships.len(), 2);
+    assert_eq!(relationships[0].kind, RelationshipKind::ManyToOne);
+    assert_eq!((relationships[0].from_field.as_deref(), relationships[0].to.as_str()), (Some("driver"), "User"));
+    assert_eq!(relationships[1].kind, RelationshipKind::ManyToMany);
+    assert_eq!((relationships[1].from_field.as_deref(), relationships[1].to.as_str()), (Some("stop"), "Stop"));
+
+    assert!(import.warnings.iter().any(|w| w.contains("Truck.tags")));
+    assert!(import.warnings.iter().any(|w| w.contains("'User' maps to the built-in User")));
+    assert!(config.clone().into_config().validate().is_ok());
+}
+
+#[test]
+fn test_import_operations_into_the_contract() {
+    let import = openapi::import(FLEET).unwrap();
+    let contract = import.config.contract.as_ref().unwrap();
+
+    let truck = contract.schemas.iter().find(|s| s.name == "Truck").unwrap();
+    let stops = truck.properties.iter().find(|p| p.name == "stops").unwrap();
+    assert_eq!(stops.schema_type, "Stop[]");
+
+    let names: Vec<&str> = contract.operations.iter().map(|o| o.name.as_str()).collect();
+    assert_eq!(names, vec!["get_trucks", "assign_driver"]);
+
+    let list = &contract.operations[0];
+    assert_eq!(list.method, HttpMethod::Get);
+    assert_eq!(list.parameters.len(), 1);
+    assert_eq!(list.parameters[0].schema_type, "TruckStatus");
+    assert_eq!(list.responses[0].body.as_deref(), Some("Truck[]"));
+    assert!(import.warnings.iter().any(|w| w.contains("'session'")));
+
+    let assign = &contract.operations[1];
+    assert_eq!(assign.operation_id.as_deref(), Some("assignDriver"));
+    assert_eq!(assign.tag.as_deref(), Some("Dispatch"));
+    assert_eq!(assign.parameters[0].location, ParameterLocation::Path);
+    assert_eq!(assign.parameters[0].description, "Truck to assign");
+    assert_eq!(assign.request_body.as_deref(), Some("Assignment"));
+    assert_eq!(assign.responses.len(), 2);
+    assert_eq!(assign.responses[1].body, None);
+}
+
+#[test]
+fn test_rejects_swagger_2_documents() {
+    let error = openapi::import("swagger: '2.0'\ninfo: {title: Old}\npaths: {}").unwrap_err();
+    assert!(error.to_string().contains("Swagger 2"));
+}
@@ -0,0 +1,171 @@
+use rhupster_core::config::Database;
+use rhupster_core::model::{FieldType, RelationshipKind};
+use rhupster_core::schema_import;
+use std::path::PathBuf;
+
+const POSTGRES: &str = r#"
+CREATE TYPE truck_status AS ENUM ('AVAILABLE', 'IN_TRANSIT');
+
+CREATE TABLE users (id BIGSERIAL PRIMARY KEY, username TEXT NOT NULL UNIQUE);
+
+CREATE TABLE public.trucks (
+    id BIGSERIAL PRIMARY KEY,
+    license_plate VARCHAR(20) NOT NULL UNIQUE,
+    status truck_status NOT NULL,
+    photo BYTEA,
+    driver_id BIGINT NOT NULL REFERENCES users(id)
+);
+
+CREATE TABLE delivery_routes (
+    id BIGSERIAL PRIMARY KEY,
+    "startsAt" TIMESTAMP WITH TIME ZONE,
+    distance NUMERIC(10, 2),
+    backup_id BIGINT
+);
+
+ALTER TABLE delivery_routes ADD CONSTRAINT fk_backup FOREIGN KEY (backup_id) REFERENCES trucks (id);
+CREATE UNIQUE INDEX ux_backup ON delivery_routes (backup_id);
+
+CREATE TABLE delivery_route_stops (
+    delivery_route_id BIGINT NOT NULL REFERENCES delivery_routes(id),
+    stop_id BIGINT NOT NULL REFERENCES trucks(id),
+    PRIMARY KEY (delivery_route_id, stop_id)
+);
+
+CREATE TABLE _sqlx_migrations (version BIGINT PRIMARY KEY);
+"#;
+
+#[test]
+fn test_import_postgres_schema() {
+    let import = schema_import::import(POSTGRES, Database::Postgres).unwrap();
+    let config = &import.config;
+    assert_eq!(config.database, Some(Database::Postgres));
+
+    let entities = config.entities.as_ref().unwrap();
+    let names: Vec<&str> = entities.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["Truck", "DeliveryRoute"]);
+
+    let truck = &entities[0];
+    assert_eq!(truck.fields.len(), 2);
+    assert!(truck.fields[0].required && truck.fields[0].unique);
+    assert_eq!(truck.fields[0].max, Some(20));
+    assert_eq!(truck.fields[1].field_type, FieldType::Enum("TruckStatus".to_string()));
+    assert_eq!(config.enums.as_ref().unwrap()[0].values, vec!["AVAILABLE", "IN_TRANSIT"]);
+
+    let route = &entities[1];
+    assert_eq!(route.fields[0].name, "starts_at");
+    assert_eq!(route.fields[0].field_type, FieldType::DateTime);
+    assert_eq!(route.fields[1].field_type, FieldType::Decimal);
+
+    let relationships = config.relationships.as_ref().unwrap();
+    let summary: Vec<(RelationshipKind, &str, &str, &str, bool)> = relationships
+        .iter()
+        .map(|r| (r.kind, r.from.as_str(), r.from_field.as_deref().unwrap(), r.to.as_str(), r.required))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (RelationshipKind::ManyToOne, "Truck", "driver", "User", true),
+            (RelationshipKind::OneToOne, "DeliveryRoute", "backup", "Truck", false),
+            (RelationshipKind::ManyToMany, "DeliveryRoute", "stop", "Truck", false),
+        ]
+    );
+
+    assert_eq!(import.warnings.len(), 2, "{:#?}", import.warnings);
+    assert!(import.warnings[0].contains("'users' maps to the built-in User"));
+    assert!(import.warnings[1].contains("'trucks.photo' has unsupported type BYTEA"));
+    assert!(import.config.clone().into_config().validate().is_ok());
+}
+
+#[test]
+fn test_import_mysql_schema() {
+    let sql = r#"
+CREATE TABLE `customers` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+  `fullName` varchar(120) NOT NULL,
+  `tier` enum('GOLD','SILVER') DEFAULT NULL,
+  `is_active` tinyint(1) NOT NULL DEFAULT 1,
+  `created` datetime NOT NULL,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `uk_name` (`fullName`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+CREATE TABLE `orders` (
+  `order_no` int NOT NULL,
+  `customer_id` bigint unsigned NOT NULL,
+  PRIMARY KEY (`order_no`),
+  CONSTRAINT `fk_customer` FOREIGN KEY (`customer_id`) REFERENCES `customers` (`id`)
+);
+"#;
+    let import = schema_import::import(sql, Database::MySQL).unwrap();
+    let entities = import.config.entities.as_ref().unwrap();
+
+    let customer = &entities[0];
+    let fields: Vec<(&str, &FieldType)> = customer.fields.iter().map(|f| (f.name.as_str(), &f.field_type)).collect();
+    assert_eq!(
+        fields,
+        vec![
+            ("full_name", &FieldType::String),
+            ("tier", &FieldType::Enum("CustomerTier".to_string())),
+            ("is_active", &FieldType::Boolean),
+            ("created", &FieldType::DateTime),
+        ]
+    );
+    assert!(customer.fields[0].unique);
+
+    // A primary key other than `id` stays as a unique field.
+    let order = &entities[1];
+    assert_eq!(order.fields[0].name, "order_no");
+    assert!(order.fields[0].unique);
+    assert!(import.warnings[0].contains("Primary key 'order_no'"));
+    assert_eq!(import.config.relationships.as_ref().unwrap()[0].to, "Customer");
+}
+
+#[test]
+fn test_import_sqlite_database_file() {
+    let dir = PathBuf::from("test_output/schemas");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("fleet.db");
+    let _ = std::fs::remove_file(&path);
+    let connection = rusqlite::Connection::open(&path).unwrap();
+    connection
+        .execute_batch(
+            "CREATE TABLE trucks (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 status TEXT NOT NULL CHECK (status IN ('AVAILABLE', 'IN_TRANSIT')),
+                 capacity REAL
+             );
+             CREATE TABLE routes (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 truck_status TEXT CHECK (truck_status IN ('AVAILABLE', 'IN_TRANSIT')),
+                 truck_id INTEGER REFERENCES trucks(id)
+             );",
+        )
+        .unwrap();
+    drop(connection);
+
+    // The dialect only applies to `.sql` files.
+    let import = schema_import::import_file(&path, Some(Database::Postgres)).unwrap();
+    assert_eq!(import.config.database, Some(Database::SQLite));
+
+    let entities = import.config.entities.as_ref().unwrap();
+    assert_eq!(entities[0].fields[0].field_type, FieldType::Enum("TruckStatus".to_string()));
+    assert_eq!(entities[0].fields[1].field_type, FieldType::Float);
+    // Both columns list the same values, so they share one enum.
+    assert_eq!(entities[1].fields[0].field_type, FieldType::Enum("TruckStatus".to_string()));
+    assert_eq!(import.config.enums.as_ref().unwrap().len(), 1);
+    assert_eq!(import.config.relationships.as_ref().unwrap()[0].from_field.as_deref(), Some("truck"));
+    assert!(import.warnings.is_empty(), "{:#?}", import.warnings);
+}
+
+#[test]
+fn test_table_and_column_names_are_converted_like_the_templates() {
+    let sql = r#"
+CREATE TABLE status (id BIGSERIAL PRIMARY KEY, "HTTPCode" INTEGER);
+CREATE TABLE addresses (id BIGSERIAL PRIMARY KEY, city TEXT);
+"#;
+    let import = schema_import::import(sql, Database::Postgres).unwrap();
+    let entities = import.config.entities.as_ref().unwrap();
+    let names: Vec<&str> = entities.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["Status", "Address"]);
+    assert_eq!(entities[0].fields[0].name, "http_code");
+}
@@ -0,0 +1,237 @@
+use rhupster_core::config::{Authentication, Database, Infrastructure, Orm, ProjectConfig, RouterStrategy};
+use rhupster_core::generator::{Generator, TemplateSource};
+use rhupster_core::model::{Entity, EnumDef, FetchType, Field, PrimaryKey, Relationship, RelationshipKind};
+use std::path::PathBuf;
+
+fn error_fields(config: &ProjectConfig) -> Vec<String> {
+    config.validate().errors.into_iter().map(|e| e.field).collect()
+}
+
+#[test]
+fn test_default_config_is_valid() {
+    let report = ProjectConfig::default().validate();
+    assert!(report.is_ok(), "{}", report);
+    assert!(report.warnings.is_empty());
+}
+
+#[test]
+fn test_orm_must_match_database() {
+    let mongo_with_diesel = ProjectConfig {
+        database: Database::MongoDB,
+        orm: Orm::Diesel,
+        ..Default::default()
+    };
+    assert_eq!(error_fields(&mongo_with_diesel), vec!["orm"]);
+
+    let sql_without_orm = ProjectConfig {
+        database: Database::MySQL,
+        orm: Orm::None,
+        ..Default::default()
+    };
+    assert_eq!(error_fields(&sql_without_orm), vec!["orm"]);
+
+    let mongo_native = ProjectConfig {
+        database: Database::MongoDB,
+        orm: Orm::None,
+        primary_key: PrimaryKey::UuidV7,
+        ..Default::default()
+    };
+    assert!(mongo_native.validate().is_ok());
+}
+
+#[test]
+fn test_oauth2_requires_providers() {
+    let config = ProjectConfig {
+        authentication: Authentication::OAuth2(vec![]),
+        ..Default::default()
+    };
+    let report = config.validate();
+    assert_eq!(report.errors.len(), 1);
+    assert!(report.errors[0].hint.is_some());
+}
+
+#[test]
+fn test_socket_requires_standard_router() {
+    let config = ProjectConfig {
+        infrastructure: vec![Infrastructure::Socket],
+        router_strategy: RouterStrategy::AxumFolderRouter,
+        ..Default::default()
+    };
+    assert_eq!(error_fields(&config), vec!["infrastructure"]);
+}
+
+#[test]
+fn test_duplicate_infrastructure_is_a_warning() {
+    let config = ProjectConfig {
+        infrastructure: vec![Infrastructure::Redis, Infrastructure::Redis],
+        ..Default::default()
+    };
+    let report = config.validate();
+    assert!(report.is_ok());
+    assert_eq!(report.warnings.len(), 1);
+}
+
+#[test]
+fn test_entity_names_are_checked() {
+    let mut truck = Entity::new("Truck");
+    truck.fields.push("plate:string".parse::<Field>().unwrap());
+    truck.fields.push("plate:string".parse::<Field>().unwrap());
+    let mut user = Entity::new("User");
+    user.fields.push("email:string".parse::<Field>().unwrap());
+    let mut bad_name = Entity::new("bad_name");
+    bad_name.fields.push("label:string".parse::<Field>().unwrap());
+    let config = ProjectConfig {
+        entities: vec![truck, user, bad_name],
+        ..Default::default()
+    };
+    assert_eq!(
+        error_fields(&config),
+        vec!["entities.Truck", "entities.User", "entities.bad_name"]
+    );
+}
+
+#[test]
+fn test_entity_fields_are_checked() {
+    let mut route = Entity::new("DeliveryRoute");
+    route.fields.push("StartsAt:datetime".parse::<Field>().unwrap());
+    let config = ProjectConfig {
+        entities: vec![route, Entity::new("Empty")],
+        ..Default::default()
+    };
+    let report = config.validate();
+    let messages: Vec<String> = report.errors.iter().map(|e| e.message.clone()).collect();
+    assert_eq!(
+        messages,
+        vec!["'StartsAt' is not a valid field name", "Entity 'Empty' has no fields"]
+    );
+}
+
+#[test]
+fn test_enums_are_checked() {
+    let mut truck = Entity::new("Truck");
+    truck.fields.push("status:TruckStatus".parse::<Field>().unwrap());
+    truck.fields.push("kind:TruckKind".parse::<Field>().unwrap());
+    let enum_def = |name: &str, values: &[&str]| EnumDef::new(name, values.iter().map(|v| v.to_string()).collect());
+    let config = ProjectConfig {
+        entities: vec![truck],
+        enums: vec![
+            enum_def("TruckStatus", &["AVAILABLE", "IN_TRANSIT"]),
+            enum_def("Long", &["SHORT"]),
+            enum_def("Empty", &[]),
+            enum_def("Level", &["LOW", "in-between", "low"]),
+        ],
+        ..Default::default()
+    };
+    let messages: Vec<String> = config.validate().errors.into_iter().map(|e| e.message).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "Field 'kind' uses undeclared enum 'TruckKind'",
+            "'Long' is already the name of a type",
+            "Enum 'Empty' has no values",
+            "'in-between' is not a valid enum value",
+            "Value 'low' is listed more than once",
+        ]
+    );
+}
+
+#[test]
+fn test_relationships_are_checked() {
+    let relationship = |from: &str, field: &str, to: &str| Relationship {
+        kind: RelationshipKind::ManyToOne,
+        from: from.to_string(),
+        from_field: Some(field.to_string()),
+        to: to.to_string(),
+        to_field: None,
+        required: false,
+        fetch: FetchType::Lazy,
+    };
+    let config = ProjectConfig {
+        relationships: vec![
+            relationship("Truck", "driver", "User"),
+            relationship("Truck", "trailer", "Trailer"),
+            relationship("User", "truck", "Truck"),
+            relationship("Truck", "license_plate", "User"),
+        ],
+        ..Default::default()
+    };
+    let messages: Vec<String> = config.validate().errors.into_iter().map(|e| e.message).collect();
+    assert_eq!(messages.len(), 3, "{:?}", messages);
+    assert!(messages[0].contains("Trailer"));
+    assert!(messages[1].contains("User"));
+    assert!(messages[2].contains("license_plate"));
+}
+
+#[test]
+fn test_mongodb_rejects_sequence_ids() {
+    let mut route = Entity::new("Route");
+    route.fields.push("code:string!".parse::<Field>().unwrap());
+    route.primary_key = Some(PrimaryKey::I64);
+    let config = ProjectConfig {
+        database: Database::MongoDB,
+        orm: Orm::None,
+        primary_key: PrimaryKey::UuidV7,
+        entities: vec![ProjectConfig::default().entities[0].clone(), route],
+        ..Default::default()
+    };
+
+    assert_eq!(error_fields(&config), ["entities.Route.primary_key"]);
+    assert!(ProjectConfig { primary_key: PrimaryKey::U64, entities: vec![], ..config }.validate().into_result().is_err());
+}
+
+#[test]
+fn test_decimal_fields_are_checked() {
+    let mut invoice = Entity::new("Invoice");
+    invoice.fields.push("total:decimal!".parse::<Field>().unwrap());
+    let config = ProjectConfig { entities: vec![invoice.clone()], ..Default::default() };
+    assert!(config.validate().errors.is_empty());
+
+    let sqlite = ProjectConfig { database: Database::SQLite, ..config.clone() };
+    assert_eq!(error_fields(&sqlite), ["entities.Invoice"]);
+
+    invoice.fields[0].min = Some(0);
+    let bounded = ProjectConfig { entities: vec![invoice], ..config };
+    let messages: Vec<String> = bounded.validate().errors.into_iter().map(|e| e.message).collect();
+    assert_eq!(messages, ["Field 'total' is a Decimal, which cannot have min or max bounds"]);
+}
+
+#[tokio::test]
+async fn test_generator_refuses_invalid_config() {
+    let config = ProjectConfig {
+        database: Database::MongoDB,
+        orm: Orm::SeaOrm,
+        ..Default::default()
+    };
+    let output_dir = PathBuf::from("test_output/invalid_config");
+    let generator = Generator::new(config, TemplateSource::Path(PathBuf::from("../templates")));
+
+    let err = generator.generate(&output_dir).await.unwrap_err();
+
+    assert!(err.to_string().contains("SeaOrm does not support MongoDB"));
+    assert!(!output_dir.exists());
+}
+
+#[test]
+fn test_contract_is_checked() {
+    let contract = serde_json::from_value(serde_json::json!({
+        "schemas": [{ "name": "Assignment", "properties": [{ "name": "truck", "type": "Vehicle" }] }],
+        "operations": [
+            { "name": "list_trucks", "method": "GET", "path": "/api/trucks" },
+            { "name": "assign", "method": "POST", "path": "/trucks/{truckId}/assign" },
+            { "name": "assign", "method": "PUT", "path": "trucks" },
+        ],
+    }))
+    .unwrap();
+    let config = ProjectConfig { contract, ..Default::default() };
+    let messages: Vec<String> = config.validate().errors.into_iter().map(|e| e.message).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "Unknown type 'Vehicle'",
+            "GET /api/trucks is already served by the generated Truck endpoints",
+            "Path parameter 'truckId' of POST /trucks/{truckId}/assign is not declared",
+            "Operation 'assign' is defined more than once",
+            "Path 'trucks' does not start with '/'",
+        ]
+    );
+}